@@ -1,16 +1,139 @@
 //======================================================================
 // auxcrypt/src/backends/mod.rs
-// Selects the appropriate permutation backend at compile time.
+// Permutation backends and runtime backend selection.
 //======================================================================
 
-use cfg_if::cfg_if;
+use core::fmt;
+use core::sync::atomic::{AtomicU8, Ordering};
+use crate::consts::STATE_WORDS;
+use crate::variant::AuxCryptVariant;
 
-cfg_if! {
-    if #[cfg(feature = "auxcrypt_simd")] {
-        pub(crate) mod simd;
-        pub(crate) use self::simd::permutation;
-    } else {
-        pub(crate) mod soft;
-        pub(crate) use self::soft::permutation;
+pub(crate) mod soft;
+
+#[cfg(all(feature = "auxcrypt_simd", target_arch = "x86_64"))]
+pub(crate) mod simd;
+
+/// Implementation used to evaluate the AuxCrypt permutation.
+///
+/// The `soft` backend is always available. The `simd` backend is compiled in
+/// with the `auxcrypt_simd` feature and is only usable when the running CPU
+/// supports it; by default the fastest available backend is picked on first use.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Backend {
+    /// Portable scalar implementation.
+    Soft,
+    /// AVX2 implementation (x86_64 only).
+    Simd,
+}
+
+/// Error returned when selecting a backend that is not available.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BackendUnavailable(pub Backend);
+
+impl fmt::Display for BackendUnavailable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the {:?} backend is not available on this build or CPU", self.0)
+    }
+}
+
+impl std::error::Error for BackendUnavailable {}
+
+// 0 = not detected yet, otherwise the value of `Backend::to_tag`.
+static SELECTED: AtomicU8 = AtomicU8::new(0);
+
+impl Backend {
+    /// Every backend, whether or not it is available.
+    pub const ALL: [Backend; 2] = [Backend::Soft, Backend::Simd];
+
+    const fn to_tag(self) -> u8 {
+        match self {
+            Backend::Soft => 1,
+            Backend::Simd => 2,
+        }
+    }
+
+    const fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            1 => Some(Backend::Soft),
+            2 => Some(Backend::Simd),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if this backend is compiled in and supported by the CPU.
+    pub fn is_available(self) -> bool {
+        match self {
+            Backend::Soft => true,
+            #[cfg(all(feature = "auxcrypt_simd", target_arch = "x86_64"))]
+            Backend::Simd => simd::is_available(),
+            #[cfg(not(all(feature = "auxcrypt_simd", target_arch = "x86_64")))]
+            Backend::Simd => false,
+        }
     }
-}
\ No newline at end of file
+
+    /// Returns the fastest backend available on the running CPU.
+    pub fn detect() -> Self {
+        if Backend::Simd.is_available() {
+            Backend::Simd
+        } else {
+            Backend::Soft
+        }
+    }
+
+    /// Returns the backend currently used by all AuxCrypt constructions.
+    pub fn active() -> Self {
+        match Self::from_tag(SELECTED.load(Ordering::Relaxed)) {
+            Some(backend) => backend,
+            None => {
+                let backend = Self::detect();
+                SELECTED.store(backend.to_tag(), Ordering::Relaxed);
+                backend
+            }
+        }
+    }
+
+    /// Forces every AuxCrypt construction in this process to use `self`.
+    ///
+    /// All backends compute the same permutation, so switching at any point
+    /// only affects speed, never outputs.
+    pub fn select(self) -> Result<(), BackendUnavailable> {
+        if !self.is_available() {
+            return Err(BackendUnavailable(self));
+        }
+        SELECTED.store(self.to_tag(), Ordering::Relaxed);
+        Ok(())
+    }
+
+    // The methods below evaluate with this backend regardless of the selection.
+    // An unavailable SIMD backend falls back to the soft implementation.
+
+    #[inline]
+    pub(crate) fn permute<V: AuxCryptVariant>(self, state: &mut [u64; STATE_WORDS]) {
+        match self {
+            #[cfg(all(feature = "auxcrypt_simd", target_arch = "x86_64"))]
+            Backend::Simd => simd::permutation::<V>(state),
+            _ => soft::permutation::<V>(state),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn permute_inverse<V: AuxCryptVariant>(self, state: &mut [u64; STATE_WORDS]) {
+        match self {
+            #[cfg(all(feature = "auxcrypt_simd", target_arch = "x86_64"))]
+            Backend::Simd => simd::permutation_inverse::<V>(state),
+            _ => soft::permutation_inverse::<V>(state),
+        }
+    }
+}
+
+/// The state permutation, evaluated with the active backend.
+#[inline]
+pub(crate) fn permutation<V: AuxCryptVariant>(state: &mut [u64; STATE_WORDS]) {
+    Backend::active().permute::<V>(state)
+}
+
+/// The inverse state permutation, evaluated with the active backend.
+#[inline]
+pub(crate) fn permutation_inverse<V: AuxCryptVariant>(state: &mut [u64; STATE_WORDS]) {
+    Backend::active().permute_inverse::<V>(state)
+}
@@ -1,96 +1,397 @@
 //======================================================================
 // auxcrypt/src/backends/simd.rs
-// Portable SIMD implementation of the AuxCrypt permutation.
+// AVX2 implementation of the AuxCrypt permutation.
 //======================================================================
 
 use crate::consts::*;
 use crate::stream::AuxCryptCore;
-use crate::variant::AuxCryptVariant;
-use cipher::{Block, BlockSizeUser, ParBlocksSizeUser, StreamBackend};
-use std::simd::*;
+use crate::variant::{AuxCryptVariant, Revision};
+use cipher::{Block, BlockSizeUser, ParBlocks, ParBlocksSizeUser, StreamBackend};
+use zeroize::Zeroize;
+use core::arch::x86_64::*;
 
-/// The portable SIMD backend for AuxCrypt.
+/// Returns `true` if the running CPU supports this backend.
+#[inline]
+pub(crate) fn is_available() -> bool {
+    std::is_x86_feature_detected!("avx2")
+}
+
+/// The AVX2 backend for AuxCrypt.
 pub struct Backend<'a, V: AuxCryptVariant>(pub(crate) &'a mut AuxCryptCore<V>);
 
 impl<'a, V: AuxCryptVariant> BlockSizeUser for Backend<'a, V> {
     type BlockSize = cipher::consts::U128;
 }
 
+/// Number of keystream blocks generated in parallel (64-bit lanes per 256-bit vector).
+pub(crate) const PAR_BLOCKS: usize = 4;
+
 impl<'a, V: AuxCryptVariant> ParBlocksSizeUser for Backend<'a, V> {
-    type ParBlocksSize = cipher::consts::U128;
+    type ParBlocksSize = cipher::consts::U4;
 }
 
 impl<'a, V: AuxCryptVariant> StreamBackend for Backend<'a, V> {
     #[inline]
     fn gen_ks_block(&mut self, output: &mut Block<Self>) {
-        self.0.counter = self.0.counter.wrapping_add(1);
+        let counter = self.0.next_block_counter();
         let mut working_state = self.0.state;
-        working_state[0] ^= self.0.counter;
+        working_state[0] ^= counter;
         permutation::<V>(&mut working_state);
         for (i, chunk) in output.chunks_exact_mut(8).enumerate() {
             chunk.copy_from_slice(&working_state[i].to_le_bytes());
         }
+        working_state.zeroize();
+    }
+
+    /// Computes four counter blocks at once, one per vector lane.
+    #[inline]
+    fn gen_par_ks_blocks(&mut self, blocks: &mut ParBlocks<Self>) {
+        let mut states = [self.0.state; PAR_BLOCKS];
+        for state in states.iter_mut() {
+            let counter = self.0.next_block_counter();
+            state[0] ^= counter;
+        }
+        permutation_x4::<V>(&mut states);
+        for (block, state) in blocks.iter_mut().zip(states.iter()) {
+            for (chunk, word) in block.chunks_exact_mut(8).zip(state.iter()) {
+                chunk.copy_from_slice(&word.to_le_bytes());
+            }
+        }
+        states.zeroize();
     }
 }
 
-/// The vectorized non-linear function f(x).
-#[inline(always)]
-fn f_vec(x: u64x4) -> u64x4 {
-    let rot_a = x.rotate_elements_left::<{ ROT_A as usize }>();
-    let rot_b = x.rotate_elements_left::<{ ROT_B as usize }>();
-    (!x) ^ rot_a ^ rot_b
+/// Bitwise left rotation of every 64-bit lane.
+#[inline]
+#[target_feature(enable = "avx2")]
+fn rotl<const L: i32, const R: i32>(x: __m256i) -> __m256i {
+    _mm256_or_si256(_mm256_slli_epi64::<L>(x), _mm256_srli_epi64::<R>(x))
 }
 
-/// A single vectorized Lai-Massey round on two vectors.
-#[inline(always)]
-fn lai_massey_round_vec(a: &mut u64x4, b: &mut u64x4) {
-    let diff = f_vec(*a ^ *b);
-    *a ^= diff;
-    *b ^= diff;
+/// The vectorized round function f(x) (see `soft.rs`).
+#[inline]
+#[target_feature(enable = "avx2")]
+fn f_vec<V: AuxCryptVariant>(x: __m256i) -> __m256i {
+    let rot_a = rotl::<{ ROT_A as i32 }, { 64 - ROT_A as i32 }>(x);
+    let rot_b = rotl::<{ ROT_B as i32 }, { 64 - ROT_B as i32 }>(x);
+    let not_x = _mm256_xor_si256(x, _mm256_set1_epi64x(-1));
+    let linear = _mm256_xor_si256(_mm256_xor_si256(not_x, rot_a), rot_b);
+    match V::REVISION {
+        Revision::V1 | Revision::V2 => linear,
+        Revision::V3 => {
+            let rot_c = rotl::<{ ROT_C as i32 }, { 64 - ROT_C as i32 }>(x);
+            let rot_d = rotl::<{ ROT_D as i32 }, { 64 - ROT_D as i32 }>(x);
+            _mm256_xor_si256(linear, _mm256_and_si256(rot_c, rot_d))
+        }
+    }
+}
+
+/// The orthomorphism σ of every lane: swap the 32-bit halves, then XOR the old low half into the new low half.
+#[inline]
+#[target_feature(enable = "avx2")]
+fn sigma_vec(x: __m256i) -> __m256i {
+    let swapped = _mm256_shuffle_epi32::<0b10_11_00_01>(x);
+    _mm256_xor_si256(swapped, _mm256_and_si256(x, _mm256_set1_epi64x(0xFFFF_FFFF)))
+}
+
+/// The inverse of [`sigma_vec`] in every lane.
+#[inline]
+#[target_feature(enable = "avx2")]
+fn sigma_inverse_vec(x: __m256i) -> __m256i {
+    let swapped = _mm256_shuffle_epi32::<0b10_11_00_01>(x);
+    _mm256_xor_si256(swapped, _mm256_and_si256(x, _mm256_set1_epi64x(0xFFFF_FFFF_0000_0000_u64 as i64)))
+}
+
+/// A Lai-Massey round between two vectors (lane `i` of `a` pairs with lane `i` of `b`).
+#[inline]
+#[target_feature(enable = "avx2")]
+fn lai_massey_round_vec<V: AuxCryptVariant>(a: &mut __m256i, b: &mut __m256i) {
+    let diff = f_vec::<V>(_mm256_xor_si256(*a, *b));
+    *a = _mm256_xor_si256(*a, diff);
+    *b = _mm256_xor_si256(*b, diff);
+    if V::REVISION == Revision::V3 {
+        *a = sigma_vec(*a);
+    }
+}
+
+/// The inverse of [`lai_massey_round_vec`].
+#[inline]
+#[target_feature(enable = "avx2")]
+fn lai_massey_round_vec_inverse<V: AuxCryptVariant>(a: &mut __m256i, b: &mut __m256i) {
+    if V::REVISION == Revision::V3 {
+        *a = sigma_inverse_vec(*a);
+    }
+    let diff = f_vec::<V>(_mm256_xor_si256(*a, *b));
+    *a = _mm256_xor_si256(*a, diff);
+    *b = _mm256_xor_si256(*b, diff);
+}
+
+/// A Lai-Massey round between the lanes of one vector, paired by the lane shuffle `IMM`.
+///
+/// `a ^ shuffle(a)` is identical in both lanes of a pair, so a single XOR updates both.
+/// `FIRST` is the `_mm256_blend_epi32` mask of the first word of each pair (where σ applies).
+#[inline]
+#[target_feature(enable = "avx2")]
+fn lai_massey_round_lanes<V: AuxCryptVariant, const IMM: i32, const FIRST: i32>(a: &mut __m256i) {
+    let diff = f_vec::<V>(_mm256_xor_si256(*a, _mm256_permute4x64_epi64::<IMM>(*a)));
+    *a = _mm256_xor_si256(*a, diff);
+    if V::REVISION == Revision::V3 {
+        *a = _mm256_blend_epi32::<FIRST>(*a, sigma_vec(*a));
+    }
+}
+
+/// The inverse of [`lai_massey_round_lanes`].
+#[inline]
+#[target_feature(enable = "avx2")]
+fn lai_massey_round_lanes_inverse<V: AuxCryptVariant, const IMM: i32, const FIRST: i32>(a: &mut __m256i) {
+    if V::REVISION == Revision::V3 {
+        *a = _mm256_blend_epi32::<FIRST>(*a, sigma_inverse_vec(*a));
+    }
+    let diff = f_vec::<V>(_mm256_xor_si256(*a, _mm256_permute4x64_epi64::<IMM>(*a)));
+    *a = _mm256_xor_si256(*a, diff);
+}
+
+/// One row of the word permutation `P`: lane c comes from lane c of row `(r + c) % 4`.
+#[inline]
+#[target_feature(enable = "avx2")]
+fn diagonal(a: __m256i, b: __m256i, c: __m256i, d: __m256i) -> __m256i {
+    _mm256_blend_epi32::<0xF0>(
+        _mm256_blend_epi32::<0x0C>(a, b),
+        _mm256_blend_epi32::<0xC0>(c, d),
+    )
 }
 
 /// The core state permutation function for AuxCrypt (SIMD version).
-#[inline(always)]
+///
+/// Falls back to the `soft` backend if the CPU lacks AVX2.
+#[inline]
 pub(crate) fn permutation<V: AuxCryptVariant>(state: &mut [u64; STATE_WORDS]) {
-    // Load state into SIMD vectors
-    let mut s0 = u64x4::from_slice(&state[0..4]);
-    let mut s1 = u64x4::from_slice(&state[4..8]);
-    let mut s2 = u64x4::from_slice(&state[8..12]);
-    let mut s3 = u64x4::from_slice(&state[12..16]);
+    if is_available() {
+        // SAFETY: AVX2 support was checked just above.
+        unsafe { permutation_avx2::<V>(state) }
+    } else {
+        super::soft::permutation::<V>(state)
+    }
+}
+
+/// The inverse permutation (SIMD version).
+///
+/// Falls back to the `soft` backend if the CPU lacks AVX2.
+#[inline]
+pub(crate) fn permutation_inverse<V: AuxCryptVariant>(state: &mut [u64; STATE_WORDS]) {
+    if is_available() {
+        // SAFETY: AVX2 support was checked just above.
+        unsafe { permutation_inverse_avx2::<V>(state) }
+    } else {
+        super::soft::permutation_inverse::<V>(state)
+    }
+}
+
+/// Applies the permutation to four independent states at once.
+///
+/// Falls back to the `soft` backend if the CPU lacks AVX2.
+#[inline]
+pub(crate) fn permutation_x4<V: AuxCryptVariant>(states: &mut [[u64; STATE_WORDS]; PAR_BLOCKS]) {
+    if is_available() {
+        // SAFETY: AVX2 support was checked just above.
+        unsafe { permutation_x4_avx2::<V>(states) }
+    } else {
+        super::soft::permutation_par::<V, PAR_BLOCKS>(states)
+    }
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn permutation_avx2<V: AuxCryptVariant>(state: &mut [u64; STATE_WORDS]) {
+    // Each vector holds one row of four words: s0 = words 0..4, s1 = 4..8, ...
+    let ptr = state.as_mut_ptr() as *mut __m256i;
+    // SAFETY: `state` is 128 bytes long, i.e. exactly four unaligned 256-bit vectors.
+    let (mut s0, mut s1, mut s2, mut s3) = unsafe {
+        (
+            _mm256_loadu_si256(ptr),
+            _mm256_loadu_si256(ptr.add(1)),
+            _mm256_loadu_si256(ptr.add(2)),
+            _mm256_loadu_si256(ptr.add(3)),
+        )
+    };
 
-    for r in 0..V::ROUNDS {
+    for round in 0..V::ROUNDS {
         // 1. Add Round Constant
-        s0[0] ^= RC[r];
+        add_round_constant::<V>([&mut s0, &mut s1, &mut s2, &mut s3], round);
 
-        // 2. Non-linear Layer (4D Lai-Massey)
-        // This is a simplified SIMD implementation focusing on vector-width operations.
-        // A more complex implementation could use shuffles for perfect dimensional mapping.
-        lai_massey_round_vec(&mut s0, &mut s1);
-        lai_massey_round_vec(&mut s2, &mut s3);
-        lai_massey_round_vec(&mut s0, &mut s2);
-        lai_massey_round_vec(&mut s1, &mut s3);
+        // 2. Non-linear Layer (4D Lai-Massey), same dimension order as `soft.rs`.
+        // Dim 1 (X): words 2i and 2i+1, i.e. adjacent lanes (first word in lanes 0 and 2).
+        lai_massey_round_lanes::<V, 0b10_11_00_01, 0x33>(&mut s0);
+        lai_massey_round_lanes::<V, 0b10_11_00_01, 0x33>(&mut s1);
+        lai_massey_round_lanes::<V, 0b10_11_00_01, 0x33>(&mut s2);
+        lai_massey_round_lanes::<V, 0b10_11_00_01, 0x33>(&mut s3);
+        // Dim 2 (Y): words 4i+j and 4i+j+2, i.e. lanes two apart (first word in lanes 0 and 1).
+        lai_massey_round_lanes::<V, 0b01_00_11_10, 0x0F>(&mut s0);
+        lai_massey_round_lanes::<V, 0b01_00_11_10, 0x0F>(&mut s1);
+        lai_massey_round_lanes::<V, 0b01_00_11_10, 0x0F>(&mut s2);
+        lai_massey_round_lanes::<V, 0b01_00_11_10, 0x0F>(&mut s3);
+        // Dim 3 (Z): words 8i+j and 8i+j+4, i.e. neighbouring rows.
+        lai_massey_round_vec::<V>(&mut s0, &mut s1);
+        lai_massey_round_vec::<V>(&mut s2, &mut s3);
+        // Dim 4 (W): words i and i+8, i.e. rows two apart.
+        lai_massey_round_vec::<V>(&mut s0, &mut s2);
+        lai_massey_round_vec::<V>(&mut s1, &mut s3);
 
         // 3. Linear Layer (Word Permutation)
-        let mut temp_state = [0u64; STATE_WORDS];
-        s0.copy_to_slice(&mut temp_state[0..4]);
-        s1.copy_to_slice(&mut temp_state[4..8]);
-        s2.copy_to_slice(&mut temp_state[8..12]);
-        s3.copy_to_slice(&mut temp_state[12..16]);
-        
-        let mut new_state_array = [0u64; STATE_WORDS];
-        for i in 0..STATE_WORDS {
-            new_state_array[i] = temp_state[P[i]];
+        (s0, s1, s2, s3) = (
+            diagonal(s0, s1, s2, s3),
+            diagonal(s1, s2, s3, s0),
+            diagonal(s2, s3, s0, s1),
+            diagonal(s3, s0, s1, s2),
+        );
+    }
+
+    // Store SIMD vectors back to state
+    // SAFETY: see the loads above.
+    unsafe {
+        _mm256_storeu_si256(ptr, s0);
+        _mm256_storeu_si256(ptr.add(1), s1);
+        _mm256_storeu_si256(ptr.add(2), s2);
+        _mm256_storeu_si256(ptr.add(3), s3);
+    }
+    for row in [&mut s0, &mut s1, &mut s2, &mut s3] {
+        row.zeroize();
+    }
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn permutation_inverse_avx2<V: AuxCryptVariant>(state: &mut [u64; STATE_WORDS]) {
+    let ptr = state.as_mut_ptr() as *mut __m256i;
+    // SAFETY: `state` is 128 bytes long, i.e. exactly four unaligned 256-bit vectors.
+    let (mut s0, mut s1, mut s2, mut s3) = unsafe {
+        (
+            _mm256_loadu_si256(ptr),
+            _mm256_loadu_si256(ptr.add(1)),
+            _mm256_loadu_si256(ptr.add(2)),
+            _mm256_loadu_si256(ptr.add(3)),
+        )
+    };
+
+    for round in (0..V::ROUNDS).rev() {
+        // 3. Inverse Linear Layer: lane c of row k goes back to row `(k - c) % 4`.
+        (s0, s1, s2, s3) = (
+            diagonal(s0, s3, s2, s1),
+            diagonal(s1, s0, s3, s2),
+            diagonal(s2, s1, s0, s3),
+            diagonal(s3, s2, s1, s0),
+        );
+
+        // 2. Inverse Non-linear Layer (dimensions in reverse order)
+        lai_massey_round_vec_inverse::<V>(&mut s0, &mut s2);
+        lai_massey_round_vec_inverse::<V>(&mut s1, &mut s3);
+        lai_massey_round_vec_inverse::<V>(&mut s0, &mut s1);
+        lai_massey_round_vec_inverse::<V>(&mut s2, &mut s3);
+        lai_massey_round_lanes_inverse::<V, 0b01_00_11_10, 0x0F>(&mut s0);
+        lai_massey_round_lanes_inverse::<V, 0b01_00_11_10, 0x0F>(&mut s1);
+        lai_massey_round_lanes_inverse::<V, 0b01_00_11_10, 0x0F>(&mut s2);
+        lai_massey_round_lanes_inverse::<V, 0b01_00_11_10, 0x0F>(&mut s3);
+        lai_massey_round_lanes_inverse::<V, 0b10_11_00_01, 0x33>(&mut s0);
+        lai_massey_round_lanes_inverse::<V, 0b10_11_00_01, 0x33>(&mut s1);
+        lai_massey_round_lanes_inverse::<V, 0b10_11_00_01, 0x33>(&mut s2);
+        lai_massey_round_lanes_inverse::<V, 0b10_11_00_01, 0x33>(&mut s3);
+
+        // 1. Remove Round Constant
+        add_round_constant::<V>([&mut s0, &mut s1, &mut s2, &mut s3], round);
+    }
+
+    // SAFETY: see the loads above.
+    unsafe {
+        _mm256_storeu_si256(ptr, s0);
+        _mm256_storeu_si256(ptr.add(1), s1);
+        _mm256_storeu_si256(ptr.add(2), s2);
+        _mm256_storeu_si256(ptr.add(3), s3);
+    }
+    for row in [&mut s0, &mut s1, &mut s2, &mut s3] {
+        row.zeroize();
+    }
+}
+
+/// Adds the round constants of `round` in the row layout (lane `c` of row `k`
+/// is word `4k + c`).
+#[inline]
+#[target_feature(enable = "avx2")]
+fn add_round_constant<V: AuxCryptVariant>(rows: [&mut __m256i; 4], round: usize) {
+    match V::REVISION {
+        Revision::V1 => *rows[0] = _mm256_xor_si256(*rows[0], _mm256_set_epi64x(0, 0, 0, RC[round] as i64)),
+        Revision::V2 | Revision::V3 => {
+            for (k, row) in rows.into_iter().enumerate() {
+                // SAFETY: `LFSR_RC[round][4 * k..]` holds at least four words, i.e. one unaligned 256-bit vector.
+                let c = unsafe { _mm256_loadu_si256(LFSR_RC[round][4 * k..].as_ptr() as *const __m256i) };
+                *row = _mm256_xor_si256(*row, c);
+            }
         }
+    }
+}
 
-        s0 = u64x4::from_slice(&new_state_array[0..4]);
-        s1 = u64x4::from_slice(&new_state_array[4..8]);
-        s2 = u64x4::from_slice(&new_state_array[8..12]);
-        s3 = u64x4::from_slice(&new_state_array[12..16]);
+/// Adds the round constants of `round` in the transposed layout.
+#[inline]
+#[target_feature(enable = "avx2")]
+fn add_round_constant_x4<V: AuxCryptVariant>(w: &mut [__m256i; STATE_WORDS], round: usize) {
+    match V::REVISION {
+        Revision::V1 => w[0] = _mm256_xor_si256(w[0], _mm256_set1_epi64x(RC[round] as i64)),
+        Revision::V2 | Revision::V3 => {
+            for (word, rc) in w.iter_mut().zip(LFSR_RC[round]) {
+                *word = _mm256_xor_si256(*word, _mm256_set1_epi64x(rc as i64));
+            }
+        }
     }
+}
 
-    // Store SIMD vectors back to state
-    s0.copy_to_slice(&mut state[0..4]);
-    s1.copy_to_slice(&mut state[4..8]);
-    s2.copy_to_slice(&mut state[8..12]);
-    s3.copy_to_slice(&mut state[12..16]);
-}
\ No newline at end of file
+/// Lai-Massey round between words `a` and `b` in the transposed layout.
+#[inline]
+#[target_feature(enable = "avx2")]
+fn lai_massey_round_words<V: AuxCryptVariant>(w: &mut [__m256i; STATE_WORDS], a: usize, b: usize) {
+    let (mut x, mut y) = (w[a], w[b]);
+    lai_massey_round_vec::<V>(&mut x, &mut y);
+    (w[a], w[b]) = (x, y);
+}
+
+/// Transposed layout: lane `b` of vector `w[j]` is word `j` of state `b`.
+/// In this layout every Lai-Massey pair is a pair of vectors and the word
+/// permutation `P` is just a renaming of vectors.
+#[target_feature(enable = "avx2")]
+unsafe fn permutation_x4_avx2<V: AuxCryptVariant>(states: &mut [[u64; STATE_WORDS]; PAR_BLOCKS]) {
+    let mut w: [__m256i; STATE_WORDS] = core::array::from_fn(|j| {
+        _mm256_set_epi64x(
+            states[3][j] as i64,
+            states[2][j] as i64,
+            states[1][j] as i64,
+            states[0][j] as i64,
+        )
+    });
+
+    for round in 0..V::ROUNDS {
+        // 1. Add Round Constant
+        add_round_constant_x4::<V>(&mut w, round);
+
+        // 2. Non-linear Layer (4D Lai-Massey)
+        for i in 0..8 { lai_massey_round_words::<V>(&mut w, 2 * i, 2 * i + 1); } // Dim 1 (X)
+        for i in 0..4 {
+            lai_massey_round_words::<V>(&mut w, 4 * i, 4 * i + 2); // Dim 2 (Y)
+            lai_massey_round_words::<V>(&mut w, 4 * i + 1, 4 * i + 3);
+        }
+        for i in 0..2 {
+            for j in 0..4 { lai_massey_round_words::<V>(&mut w, 8 * i + j, 8 * i + j + 4); } // Dim 3 (Z)
+        }
+        for i in 0..8 { lai_massey_round_words::<V>(&mut w, i, i + 8); } // Dim 4 (W)
+
+        // 3. Linear Layer (Word Permutation)
+        w = core::array::from_fn(|i| w[P[i]]);
+    }
+
+    let mut lanes = [0u64; PAR_BLOCKS];
+    for (j, word) in w.iter().enumerate() {
+        // SAFETY: `lanes` is exactly 256 bits long.
+        unsafe { _mm256_storeu_si256(lanes.as_mut_ptr() as *mut __m256i, *word) };
+        for (state, lane) in states.iter_mut().zip(lanes.iter()) {
+            state[j] = *lane;
+        }
+    }
+    // The vectors and the lane buffer held keyed state.
+    w.zeroize();
+    lanes.zeroize();
+}
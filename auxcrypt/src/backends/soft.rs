@@ -5,8 +5,9 @@
 
 use crate::consts::*;
 use crate::stream::AuxCryptCore;
-use crate::variant::AuxCryptVariant;
-use cipher::{Block, BlockSizeUser, ParBlocksSizeUser, StreamBackend};
+use crate::variant::{AuxCryptVariant, Revision};
+use cipher::{Block, BlockSizeUser, ParBlocks, ParBlocksSizeUser, StreamBackend};
+use zeroize::Zeroize;
 
 /// The software (scalar) backend for AuxCrypt.
 pub struct Backend<'a, V: AuxCryptVariant>(pub(crate) &'a mut AuxCryptCore<V>);
@@ -15,55 +16,128 @@ impl<'a, V: AuxCryptVariant> BlockSizeUser for Backend<'a, V> {
     type BlockSize = cipher::consts::U128;
 }
 
+/// Number of keystream blocks generated in parallel.
+pub(crate) const PAR_BLOCKS: usize = 4;
+
 impl<'a, V: AuxCryptVariant> ParBlocksSizeUser for Backend<'a, V> {
-    type ParBlocksSize = cipher::consts::U128;
+    type ParBlocksSize = cipher::consts::U4;
 }
 
 impl<'a, V: AuxCryptVariant> StreamBackend for Backend<'a, V> {
     #[inline]
     fn gen_ks_block(&mut self, output: &mut Block<Self>) {
-        self.0.counter = self.0.counter.wrapping_add(1);
+        let counter = self.0.next_block_counter();
         let mut working_state = self.0.state;
-        working_state[0] ^= self.0.counter;
+        working_state[0] ^= counter;
         permutation::<V>(&mut working_state);
         for (i, chunk) in output.chunks_exact_mut(8).enumerate() {
             chunk.copy_from_slice(&working_state[i].to_le_bytes());
         }
+        working_state.zeroize();
+    }
+
+    /// Computes `PAR_BLOCKS` counter blocks with interleaved permutations.
+    #[inline]
+    fn gen_par_ks_blocks(&mut self, blocks: &mut ParBlocks<Self>) {
+        let mut states = [self.0.state; PAR_BLOCKS];
+        for state in states.iter_mut() {
+            let counter = self.0.next_block_counter();
+            state[0] ^= counter;
+        }
+        permutation_par::<V, PAR_BLOCKS>(&mut states);
+        for (block, state) in blocks.iter_mut().zip(states.iter()) {
+            for (chunk, word) in block.chunks_exact_mut(8).zip(state.iter()) {
+                chunk.copy_from_slice(&word.to_le_bytes());
+            }
+        }
+        states.zeroize();
     }
 }
 
-/// The non-linear function f(x) = (¬x) ⊕ (x <<< R_A) ⊕ (x <<< R_B).
+/// The round function f(x) = (¬x) ⊕ (x <<< R_A) ⊕ (x <<< R_B), plus
+/// (x <<< R_C) ∧ (x <<< R_D) in [`Revision::V3`].
 #[inline(always)]
-fn f(x: u64) -> u64 {
-    (!x) ^ x.rotate_left(ROT_A) ^ x.rotate_left(ROT_B)
+fn f<V: AuxCryptVariant>(x: u64) -> u64 {
+    let linear = (!x) ^ x.rotate_left(ROT_A) ^ x.rotate_left(ROT_B);
+    match V::REVISION {
+        Revision::V1 | Revision::V2 => linear,
+        Revision::V3 => linear ^ (x.rotate_left(ROT_C) & x.rotate_left(ROT_D)),
+    }
+}
+
+/// The orthomorphism σ(hi ‖ lo) = lo ‖ (hi ⊕ lo) of [`Revision::V3`].
+#[inline(always)]
+fn sigma(x: u64) -> u64 {
+    x.rotate_left(32) ^ (x & 0xFFFF_FFFF)
+}
+
+/// The inverse of [`sigma`]: σ⁻¹(a ‖ b) = (a ⊕ b) ‖ a.
+#[inline(always)]
+fn sigma_inverse(x: u64) -> u64 {
+    x.rotate_left(32) ^ (x & 0xFFFF_FFFF_0000_0000)
 }
 
 /// A single Lai-Massey round on a pair of words.
 #[inline(always)]
-fn lai_massey_round(state: &mut [u64; STATE_WORDS], aidx: usize, bidx: usize) {
-    let diff = f(state[aidx] ^ state[bidx]);
+fn lai_massey_round<V: AuxCryptVariant>(state: &mut [u64; STATE_WORDS], aidx: usize, bidx: usize) {
+    let diff = f::<V>(state[aidx] ^ state[bidx]);
     state[aidx] ^= diff;
     state[bidx] ^= diff;
+    if V::REVISION == Revision::V3 {
+        state[aidx] = sigma(state[aidx]);
+    }
+}
+
+/// The inverse of [`lai_massey_round`]. Without σ the step is an involution.
+#[inline(always)]
+fn lai_massey_round_inverse<V: AuxCryptVariant>(state: &mut [u64; STATE_WORDS], aidx: usize, bidx: usize) {
+    if V::REVISION == Revision::V3 {
+        state[aidx] = sigma_inverse(state[aidx]);
+    }
+    let diff = f::<V>(state[aidx] ^ state[bidx]);
+    state[aidx] ^= diff;
+    state[bidx] ^= diff;
+}
+
+/// A single Lai-Massey round on the same pair of words in `N` states.
+#[inline(always)]
+fn lai_massey_round_par<V: AuxCryptVariant, const N: usize>(states: &mut [[u64; STATE_WORDS]; N], aidx: usize, bidx: usize) {
+    for state in states.iter_mut() {
+        lai_massey_round::<V>(state, aidx, bidx);
+    }
+}
+
+/// Adds the round constants of `round`, as defined by the variant's revision.
+#[inline(always)]
+fn add_round_constant<V: AuxCryptVariant>(state: &mut [u64; STATE_WORDS], round: usize) {
+    match V::REVISION {
+        Revision::V1 => state[0] ^= RC[round],
+        Revision::V2 | Revision::V3 => {
+            for (word, rc) in state.iter_mut().zip(LFSR_RC[round]) {
+                *word ^= rc;
+            }
+        }
+    }
 }
 
 /// The core state permutation function for AuxCrypt.
 #[inline(always)]
 pub(crate) fn permutation<V: AuxCryptVariant>(state: &mut [u64; STATE_WORDS]) {
-    for r in 0..V::ROUNDS {
+    for round in 0..V::ROUNDS {
         // 1. Add Round Constant
-        state[0] ^= RC[r];
+        add_round_constant::<V>(state, round);
 
         // 2. Non-linear Layer (4D Lai-Massey)
         // Apply rounds across 4 dimensions of the 2x2x2x2 state hypercube.
-        for i in 0..8 { lai_massey_round(state, 2 * i, 2 * i + 1); } // Dim 1 (X)
+        for i in 0..8 { lai_massey_round::<V>(state, 2 * i, 2 * i + 1); } // Dim 1 (X)
         for i in 0..4 {
-            lai_massey_round(state, 4 * i, 4 * i + 2); // Dim 2 (Y)
-            lai_massey_round(state, 4 * i + 1, 4 * i + 3);
+            lai_massey_round::<V>(state, 4 * i, 4 * i + 2); // Dim 2 (Y)
+            lai_massey_round::<V>(state, 4 * i + 1, 4 * i + 3);
         }
         for i in 0..2 {
-            for j in 0..4 { lai_massey_round(state, 8 * i + j, 8 * i + j + 4); } // Dim 3 (Z)
+            for j in 0..4 { lai_massey_round::<V>(state, 8 * i + j, 8 * i + j + 4); } // Dim 3 (Z)
         }
-        for i in 0..8 { lai_massey_round(state, i, i + 8); } // Dim 4 (W)
+        for i in 0..8 { lai_massey_round::<V>(state, i, i + 8); } // Dim 4 (W)
 
         // 3. Linear Layer (Word Permutation)
         let mut new_state = [0u64; STATE_WORDS];
@@ -72,4 +146,61 @@ pub(crate) fn permutation<V: AuxCryptVariant>(state: &mut [u64; STATE_WORDS]) {
         }
         *state = new_state;
     }
-}
\ No newline at end of file
+}
+
+/// Applies the permutation to `N` independent states, interleaving their
+/// operations so the CPU can overlap them.
+#[inline(always)]
+pub(crate) fn permutation_par<V: AuxCryptVariant, const N: usize>(states: &mut [[u64; STATE_WORDS]; N]) {
+    for round in 0..V::ROUNDS {
+        // 1. Add Round Constant
+        for state in states.iter_mut() {
+            add_round_constant::<V>(state, round);
+        }
+
+        // 2. Non-linear Layer (4D Lai-Massey)
+        for i in 0..8 { lai_massey_round_par::<V, N>(states, 2 * i, 2 * i + 1); } // Dim 1 (X)
+        for i in 0..4 {
+            lai_massey_round_par::<V, N>(states, 4 * i, 4 * i + 2); // Dim 2 (Y)
+            lai_massey_round_par::<V, N>(states, 4 * i + 1, 4 * i + 3);
+        }
+        for i in 0..2 {
+            for j in 0..4 { lai_massey_round_par::<V, N>(states, 8 * i + j, 8 * i + j + 4); } // Dim 3 (Z)
+        }
+        for i in 0..8 { lai_massey_round_par::<V, N>(states, i, i + 8); } // Dim 4 (W)
+
+        // 3. Linear Layer (Word Permutation)
+        for state in states.iter_mut() {
+            *state = core::array::from_fn(|i| state[P[i]]);
+        }
+    }
+}
+
+/// The inverse of [`permutation`].
+///
+/// A round is undone by inverting its Lai-Massey steps in reverse order.
+#[inline(always)]
+pub(crate) fn permutation_inverse<V: AuxCryptVariant>(state: &mut [u64; STATE_WORDS]) {
+    for round in (0..V::ROUNDS).rev() {
+        // 3. Inverse Linear Layer
+        let mut old_state = [0u64; STATE_WORDS];
+        for i in 0..STATE_WORDS {
+            old_state[P[i]] = state[i];
+        }
+        *state = old_state;
+
+        // 2. Inverse Non-linear Layer (dimensions in reverse order)
+        for i in 0..8 { lai_massey_round_inverse::<V>(state, i, i + 8); } // Dim 4 (W)
+        for i in 0..2 {
+            for j in 0..4 { lai_massey_round_inverse::<V>(state, 8 * i + j, 8 * i + j + 4); } // Dim 3 (Z)
+        }
+        for i in 0..4 {
+            lai_massey_round_inverse::<V>(state, 4 * i, 4 * i + 2); // Dim 2 (Y)
+            lai_massey_round_inverse::<V>(state, 4 * i + 1, 4 * i + 3);
+        }
+        for i in 0..8 { lai_massey_round_inverse::<V>(state, 2 * i, 2 * i + 1); } // Dim 1 (X)
+
+        // 1. Remove Round Constant
+        add_round_constant::<V>(state, round);
+    }
+}
@@ -6,15 +6,57 @@
 /// Number of 64-bit words in the state.
 pub const STATE_WORDS: usize = 16;
 
+/// The internal state size in bytes.
+pub const STATE_BYTES: usize = STATE_WORDS * 8;
+
 /// Rotation constants for the non-linear function `f`.
 pub const ROT_A: u32 = 19;
 pub const ROT_B: u32 = 41;
 
+/// Rotation constants of the quadratic term `(x <<< R_C) ∧ (x <<< R_D)` that
+/// [`Revision::V3`](crate::variant::Revision::V3) adds to `f`.
+pub const ROT_C: u32 = 7;
+pub const ROT_D: u32 = 53;
+
 /// Round constants (RC) - simple iota values.
 pub const RC: [u64; 20] = [
     0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19,
 ];
 
+/// LFSR seed: the first 64 fractional bits of e (a nothing-up-my-sleeve value,
+/// distinct from the one YSC2 uses).
+pub const LFSR_SEED: u64 = 0xB7E1_5162_8AED_2A6A;
+
+/// One step of the xorshift64 (13, 7, 17) generator, a maximal-length LFSR
+/// over GF(2) with period 2^64 - 1.
+#[inline(always)]
+pub const fn lfsr_next(x: u64) -> u64 {
+    let x = x ^ (x << 13);
+    let x = x ^ (x >> 7);
+    x ^ (x << 17)
+}
+
+/// Round constants of [`Revision::V2`](crate::variant::Revision::V2), one per
+/// state word and round.
+///
+/// Word `i` of round `r` is the LFSR output after `16 * r + i + 1` steps from
+/// [`LFSR_SEED`]. Like [`RC`], the table covers up to 20 rounds.
+pub const LFSR_RC: [[u64; STATE_WORDS]; 20] = {
+    let mut table = [[0u64; STATE_WORDS]; 20];
+    let mut x = LFSR_SEED;
+    let mut round = 0;
+    while round < 20 {
+        let mut i = 0;
+        while i < STATE_WORDS {
+            x = lfsr_next(x);
+            table[round][i] = x;
+            i += 1;
+        }
+        round += 1;
+    }
+    table
+};
+
 /// Permutation table for the linear layer.
 pub const P: [usize; 16] = [
     0, 5, 10, 15, 4, 9, 14, 3, 8, 13, 2, 7, 12, 1, 6, 11,
@@ -5,24 +5,29 @@
 
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
-
-#![cfg(feature = "auxcrypt_simd")]
-#![feature(portable_simd)]
-
 // --- Module Declarations ---
 
 mod backends;
-mod consts;
+pub mod consts;
 pub mod variant;
 pub mod stream;
+pub mod permutation;
 
 // --- Test Module ---
 #[cfg(test)]
 mod tests;
 
+// --- Test Support (shared with the ysc2 test suite) ---
+#[cfg(any(test, feature = "test-support"))]
+#[doc(hidden)]
+pub mod test_support;
+
 // --- Re-exports ---
 
 pub use cipher;
+pub use backends::{Backend, BackendUnavailable};
+pub use consts::{STATE_BYTES, STATE_WORDS};
+pub use permutation::{AuxCryptPermutation, AuxCryptState};
 
 // --- Top-level Type Aliases ---
 
@@ -7,21 +7,100 @@ use crate::backends;
 use crate::consts::STATE_WORDS;
 use crate::variant::AuxCryptVariant;
 use cipher::{
-    consts::U128, BlockSizeUser, Iv, IvSizeUser, Key, KeyIvInit, KeySizeUser,
+    consts::U128, BlockSizeUser, Counter, Iv, IvSizeUser, Key, KeyIvInit, KeySizeUser, OverflowError,
     StreamCipherCore, StreamCipherCoreWrapper, StreamCipherSeekCore
 };
 use core::marker::PhantomData;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Number of keystream blocks available for one key and nonce.
+///
+/// The block at position `p` XORs the 64-bit counter value `(p + 1) mod 2^64`
+/// into the state, so positions `0..KEYSTREAM_BLOCKS` are all distinct.
+pub const KEYSTREAM_BLOCKS: u128 = 1 << 64;
+
+/// A keystream block position, the counter of [`StreamCipherSeekCore`].
+///
+/// Conversions from integers reject positions at or past [`KEYSTREAM_BLOCKS`],
+/// so `try_seek` to an offset with no keystream left returns an error instead
+/// of panicking. The position after the last block can still be read back.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BlockPos(u128);
+
+impl TryFrom<u128> for BlockPos {
+    type Error = OverflowError;
+
+    fn try_from(pos: u128) -> Result<Self, OverflowError> {
+        if pos < KEYSTREAM_BLOCKS { Ok(BlockPos(pos)) } else { Err(OverflowError) }
+    }
+}
+
+impl From<BlockPos> for u128 {
+    fn from(pos: BlockPos) -> u128 {
+        pos.0
+    }
+}
+
+/// Conversions between `BlockPos` and the other integer types, through `u128`.
+macro_rules! impl_block_pos_conversions {
+    ($($int:ty),*) => {$(
+        impl TryFrom<$int> for BlockPos {
+            type Error = OverflowError;
+
+            fn try_from(pos: $int) -> Result<Self, OverflowError> {
+                u128::try_from(pos).map_err(|_| OverflowError)?.try_into()
+            }
+        }
+
+        impl TryFrom<BlockPos> for $int {
+            type Error = OverflowError;
+
+            fn try_from(pos: BlockPos) -> Result<Self, OverflowError> {
+                <$int>::try_from(pos.0).map_err(|_| OverflowError)
+            }
+        }
+    )*};
+}
+
+impl_block_pos_conversions!(i32, u32, u64, usize);
+
+impl Counter for BlockPos {}
 
 /// The core engine for the AuxCrypt stream cipher.
+///
+/// `try_apply_keystream` fails once [`KEYSTREAM_BLOCKS`] blocks have been
+/// used and seeking to or past the end returns an error (see [`BlockPos`]);
+/// generating a block past that point through the core panics. The keyed
+/// state is wiped on drop.
+#[derive(Zeroize, ZeroizeOnDrop)]
 pub struct AuxCryptCore<V: AuxCryptVariant> {
     /// The 1024-bit internal state (16 x 64-bit words).
     pub(crate) state: [u64; STATE_WORDS],
-    /// The 64-bit block counter.
-    pub(crate) counter: u64,
+    /// The block position: the number of keystream blocks consumed so far.
+    pub(crate) counter: u128,
     /// PhantomData to associate the core with a specific `AuxCryptVariant`.
     _variant: PhantomData<V>,
 }
 
+impl<V: AuxCryptVariant> AuxCryptCore<V> {
+    /// Returns the counter value for the next block and advances the position.
+    #[inline(always)]
+    pub(crate) fn next_block_counter(&mut self) -> u64 {
+        assert!(self.counter < KEYSTREAM_BLOCKS, "AuxCrypt keystream exhausted");
+        self.counter += 1;
+        self.counter as u64
+    }
+
+    /// Runs the keystream closure with `backend`, regardless of the selection.
+    pub(crate) fn process_with(&mut self, backend: backends::Backend, f: impl cipher::StreamClosure<BlockSize = cipher::consts::U128>) {
+        match backend {
+            #[cfg(all(feature = "auxcrypt_simd", target_arch = "x86_64"))]
+            backends::Backend::Simd => f.call(&mut backends::simd::Backend(self)),
+            _ => f.call(&mut backends::soft::Backend(self)),
+        }
+    }
+}
+
 impl<V: AuxCryptVariant> KeySizeUser for AuxCryptCore<V> {
     type KeySize = V::KeySize;
 }
@@ -52,32 +131,32 @@ impl<V: AuxCryptVariant> KeyIvInit for AuxCryptCore<V> {
         // Run an initial permutation to mix key and IV.
         backends::permutation::<V>(&mut state);
         
-        Self {
+        let core = Self {
             state,
             counter: 0,
             _variant: PhantomData,
-        }
+        };
+        state.zeroize();
+        core
     }
 }
 
 impl<V: AuxCryptVariant> StreamCipherCore for AuxCryptCore<V> {
-    fn remaining_blocks(&self) -> Option<usize> { None }
+    /// Saturates to `usize::MAX`, which no slice can exceed anyway.
+    fn remaining_blocks(&self) -> Option<usize> {
+        let remaining = KEYSTREAM_BLOCKS.saturating_sub(self.counter);
+        Some(usize::try_from(remaining).unwrap_or(usize::MAX))
+    }
 
     fn process_with_backend(&mut self, f: impl cipher::StreamClosure<BlockSize = Self::BlockSize>) {
-        cfg_if::cfg_if! {
-            if #[cfg(feature = "auxcrypt_simd")] {
-                f.call(&mut backends::simd::Backend(self));
-            } else {
-                f.call(&mut backends::soft::Backend(self));
-            }
-        }
+        self.process_with(backends::Backend::active(), f);
     }
 }
 
 impl<V: AuxCryptVariant> StreamCipherSeekCore for AuxCryptCore<V> {
-    type Counter = u64;
-    fn get_block_pos(&self) -> Self::Counter { self.counter }
-    fn set_block_pos(&mut self, pos: Self::Counter) { self.counter = pos; }
+    type Counter = BlockPos;
+    fn get_block_pos(&self) -> Self::Counter { BlockPos(self.counter) }
+    fn set_block_pos(&mut self, pos: Self::Counter) { self.counter = pos.0; }
 }
 
 /// The high-level stream cipher type for AuxCrypt.
@@ -0,0 +1,67 @@
+//======================================================================
+// auxcrypt/src/test_support.rs
+// Test helpers shared by the auxcrypt and ysc2 test suites.
+//======================================================================
+
+//! Helpers for the backend and zeroization tests of this crate and of `ysc2`.
+//!
+//! Compiled for this crate's tests and with the `test-support` feature,
+//! which `ysc2` enables as a dev-dependency. Not part of the stable API.
+
+use cipher::consts::U128;
+use cipher::generic_array::GenericArray;
+use cipher::typenum::Unsigned;
+use cipher::{BlockSizeUser, ParBlocks, StreamBackend, StreamClosure};
+
+/// Runs a keystream through a backend: parallel batches, then single blocks for the tail.
+pub struct KeystreamInto<'a>(pub &'a mut [u8]);
+
+impl BlockSizeUser for KeystreamInto<'_> {
+    type BlockSize = U128;
+}
+
+impl StreamClosure for KeystreamInto<'_> {
+    fn call<B: StreamBackend<BlockSize = Self::BlockSize>>(self, backend: &mut B) {
+        let batch = 128 * B::ParBlocksSize::USIZE;
+        for chunk in self.0.chunks_mut(batch) {
+            if chunk.len() == batch {
+                let mut blocks = ParBlocks::<B>::default();
+                backend.gen_par_ks_blocks(&mut blocks);
+                for (out, block) in chunk.chunks_mut(128).zip(blocks.iter()) {
+                    out.copy_from_slice(block);
+                }
+            } else {
+                for out in chunk.chunks_mut(128) {
+                    backend.gen_ks_block(out.into());
+                }
+            }
+        }
+    }
+}
+
+/// Asserts that one parallel batch from `par` equals as many single blocks from `serial`.
+pub fn assert_par_blocks_match_serial<P, S>(par: &mut P, serial: &mut S)
+where
+    P: StreamBackend<BlockSize = U128>,
+    S: StreamBackend<BlockSize = U128>,
+{
+    let mut par_blocks = ParBlocks::<P>::default();
+    par.gen_par_ks_blocks(&mut par_blocks);
+    for block in par_blocks.iter() {
+        let mut expected = GenericArray::default();
+        serial.gen_ks_block(&mut expected);
+        assert_eq!(block, &expected, "Parallel block should match the serial block");
+    }
+}
+
+/// Drops `value` in place and reports whether its memory is all zero
+/// afterwards. Only meaningful for types without padding bytes.
+pub fn wiped_on_drop<T>(value: T) -> bool {
+    let mut slot = core::mem::MaybeUninit::new(value);
+    // SAFETY: the slot holds an initialised value, which is dropped exactly
+    // once; its bytes stay readable afterwards.
+    unsafe {
+        slot.assume_init_drop();
+        core::slice::from_raw_parts(slot.as_ptr().cast::<u8>(), size_of::<T>()).iter().all(|&b| b == 0)
+    }
+}
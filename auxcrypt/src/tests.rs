@@ -4,6 +4,7 @@
 #![cfg(test)]
 
 use crate::{AuxCrypt1024Stream, AuxCrypt512Stream};
+use crate::test_support::{wiped_on_drop, KeystreamInto};
 use cipher::{KeyIvInit, StreamCipher, StreamCipherSeek};
 
 const PLAINTEXT: &[u8] = b"Test message for the AuxCrypt auxiliary stream cipher.";
@@ -45,31 +46,396 @@ fn auxcrypt_1024_stream_seek_consistency() {
 }
 
 #[test]
-#[cfg(feature = "auxcrypt_simd")]
+fn auxcrypt_stream_exhaustion_and_deep_seek() {
+    use crate::stream::KEYSTREAM_BLOCKS;
+
+    let key = [0x55; 64].into();
+    let nonce = [0x66; 64].into();
+
+    // Seek into the last block, beyond what a u64 byte offset can address.
+    let offset = (KEYSTREAM_BLOCKS - 1) * 128 + 100;
+    let mut cipher = AuxCrypt512Stream::new(&key, &nonce);
+    cipher.seek(offset);
+    assert_eq!(cipher.current_pos::<u128>(), offset);
+
+    let mut data = [0u8; 29];
+    assert!(cipher.try_apply_keystream(&mut data).is_err(), "Running past the end should fail");
+    assert_eq!(data, [0u8; 29], "A rejected call should leave the data untouched");
+    cipher.try_apply_keystream(&mut data[..28]).expect("the rest of the last block should be available");
+    assert!(cipher.try_apply_keystream(&mut [0u8; 1]).is_err(), "An exhausted keystream should not wrap");
+
+    // Seeking to the end or past it fails without panicking or moving.
+    let mut cipher = AuxCrypt512Stream::new(&key, &nonce);
+    cipher.seek(300u64);
+    for target in [KEYSTREAM_BLOCKS * 128, KEYSTREAM_BLOCKS * 128 + 5, u128::MAX] {
+        assert!(cipher.try_seek(target).is_err(), "Seeking to {target} should fail");
+    }
+    assert_eq!(cipher.current_pos::<u128>(), 300);
+}
+
+#[test]
+#[cfg(all(feature = "auxcrypt_simd", target_arch = "x86_64"))]
 fn auxcrypt_simd_vs_soft_consistency() {
+    use crate::backends::{self, Backend};
     use crate::stream::AuxCryptCore;
     use crate::variant::AuxCrypt512;
 
+    if !Backend::Simd.is_available() {
+        return;
+    }
+
     let key = [0xAB; 64].into();
     let nonce = [0xCD; 64].into();
-    let mut buffer = vec![0u8; 512];
+    let buffer = vec![0u8; 512];
 
-    // Generate keystream using the default (soft) backend via high-level API
-    let mut soft_cipher = AuxCrypt512Stream::new(&key, &nonce);
+    // Drive each backend directly so the result does not depend on runtime selection.
+    let mut soft_core = AuxCryptCore::<AuxCrypt512>::new(&key, &nonce);
+    let mut soft_backend = backends::soft::Backend(&mut soft_core);
     let mut soft_keystream = buffer.clone();
-    soft_cipher.apply_keystream(&mut soft_keystream);
 
-    // Generate keystream using the SIMD backend directly for this test
-    // This requires building a core instance and calling the backend.
     let mut simd_core = AuxCryptCore::<AuxCrypt512>::new(&key, &nonce);
-    let mut simd_backend = crate::backends::simd::Backend(&mut simd_core);
+    let mut simd_backend = backends::simd::Backend(&mut simd_core);
     let mut simd_keystream = buffer.clone();
     
     // We need to call gen_ks_block manually for each block
-    for chunk in simd_keystream.chunks_mut(128) {
-        let block = cipher::generic_array::GenericArray::from_mut_slice(chunk);
+    for (soft_chunk, simd_chunk) in soft_keystream.chunks_mut(128).zip(simd_keystream.chunks_mut(128)) {
+        let block = cipher::generic_array::GenericArray::from_mut_slice(soft_chunk);
+        cipher::StreamBackend::gen_ks_block(&mut soft_backend, block);
+        let block = cipher::generic_array::GenericArray::from_mut_slice(simd_chunk);
         cipher::StreamBackend::gen_ks_block(&mut simd_backend, block);
     }
     
     assert_eq!(soft_keystream, simd_keystream, "SIMD and Soft backends must produce identical keystreams");
-}
\ No newline at end of file
+}
+
+#[test]
+fn auxcrypt_permutation_matches_keystream() {
+    use crate::stream::AuxCryptCore;
+    use crate::variant::AuxCrypt512;
+    use crate::{AuxCryptPermutation, AuxCryptState};
+
+    let key = [0x55; 64].into();
+    let nonce = [0x66; 64].into();
+    let core = AuxCryptCore::<AuxCrypt512>::new(&key, &nonce);
+
+    // The first keystream block is P(state ^ 1).
+    let mut state = AuxCryptState::from_words(core.state);
+    state.xor_in(0, &[1]);
+    state.permute::<AuxCrypt512>();
+
+    let mut bytes = AuxCryptState::from_words(core.state).to_bytes();
+    bytes[0] ^= 1;
+    AuxCryptPermutation::<AuxCrypt512>::permute_bytes(&mut bytes);
+
+    let mut keystream = [0u8; 128];
+    let mut cipher = AuxCrypt512Stream::new(&key, &nonce);
+    cipher.apply_keystream(&mut keystream);
+
+    assert_eq!(state.to_bytes(), keystream, "Public permutation should match the stream core");
+    assert_eq!(bytes, keystream, "Byte-oriented permutation should match the stream core");
+}
+
+#[test]
+fn auxcrypt_inverse_permutation_roundtrip() {
+    use crate::backends::soft;
+    use crate::variant::{AuxCrypt1024, AuxCrypt512};
+    use crate::AuxCryptState;
+
+    let mut words = [0u64; 16];
+    for (i, word) in words.iter_mut().enumerate() {
+        *word = (i as u64 + 1).wrapping_mul(0xA076_1D64_78BD_642F);
+    }
+
+    let mut state = words;
+    soft::permutation::<AuxCrypt512>(&mut state);
+    assert_ne!(state, words, "Permutation should change the state");
+    soft::permutation_inverse::<AuxCrypt512>(&mut state);
+    assert_eq!(state, words, "Soft inverse should undo the permutation");
+
+    #[cfg(all(feature = "auxcrypt_simd", target_arch = "x86_64"))]
+    if crate::Backend::Simd.is_available() {
+        use crate::backends::simd;
+
+        simd::permutation::<AuxCrypt1024>(&mut state);
+        let mut soft_inverse = state;
+        soft::permutation_inverse::<AuxCrypt1024>(&mut soft_inverse);
+        simd::permutation_inverse::<AuxCrypt1024>(&mut state);
+        assert_eq!(state, soft_inverse, "SIMD and Soft inverses must agree");
+        assert_eq!(state, words, "SIMD inverse should undo the permutation");
+    }
+
+    let mut public = AuxCryptState::from_words(words);
+    public.permute::<AuxCrypt1024>();
+    public.permute_inverse::<AuxCrypt1024>();
+    assert_eq!(public.words(), &words, "Public inverse should round-trip");
+}
+
+#[test]
+fn auxcrypt_revision_1_outputs_are_unchanged() {
+    use crate::variant::AuxCrypt512;
+    use crate::AuxCryptPermutation;
+
+    let mut words: [u64; 16] = core::array::from_fn(|i| (i as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    AuxCryptPermutation::<AuxCrypt512>::permute(&mut words);
+    assert_eq!((words[0], words[15]), (0x23e4_0c9f_ddf4_3cdd, 0xf6da_438c_4e45_767c));
+}
+
+#[test]
+fn auxcrypt_revision_2_lfsr_constants() {
+    use crate::consts::{lfsr_next, LFSR_RC, LFSR_SEED};
+    use crate::variant::{AuxCrypt512, AuxCrypt512V2};
+    use crate::AuxCryptPermutation;
+
+    assert_eq!(LFSR_RC[0][0], lfsr_next(LFSR_SEED));
+    assert_eq!(LFSR_RC[1][0], lfsr_next(LFSR_RC[0][15]), "Rounds should continue one LFSR sequence");
+
+    let words: [u64; 16] = core::array::from_fn(|i| (i as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    let mut v1 = words;
+    let mut v2 = words;
+    AuxCryptPermutation::<AuxCrypt512>::permute(&mut v1);
+    AuxCryptPermutation::<AuxCrypt512V2>::permute(&mut v2);
+    assert_ne!(v1, v2, "Revisions should be different permutations");
+    AuxCryptPermutation::<AuxCrypt512V2>::permute_inverse(&mut v2);
+    assert_eq!(v2, words, "Revision 2 inverse should round-trip");
+
+    #[cfg(all(feature = "auxcrypt_simd", target_arch = "x86_64"))]
+    if crate::Backend::Simd.is_available() {
+        use crate::backends::{simd, soft};
+        use crate::variant::AuxCrypt1024V2;
+
+        let mut state = words;
+        simd::permutation::<AuxCrypt1024V2>(&mut state);
+        let mut expected = words;
+        soft::permutation::<AuxCrypt1024V2>(&mut expected);
+        assert_eq!(state, expected, "SIMD and Soft revision 2 must agree");
+        simd::permutation_inverse::<AuxCrypt1024V2>(&mut state);
+        assert_eq!(state, words, "SIMD revision 2 inverse should round-trip");
+
+        let mut states: [[u64; 16]; 4] = core::array::from_fn(|b| core::array::from_fn(|i| words[i] ^ b as u64));
+        let mut serial = states;
+        simd::permutation_x4::<AuxCrypt1024V2>(&mut states);
+        for state in serial.iter_mut() {
+            soft::permutation::<AuxCrypt1024V2>(state);
+        }
+        assert_eq!(states, serial, "Four-way SIMD revision 2 must agree with Soft");
+    }
+}
+
+/// XOR of `g` over the cube `x ⊕ span(directions)`.
+///
+/// If `g` has algebraic degree below `directions.len()`, this is zero for
+/// every `x` and every choice of directions.
+fn cube_sum(g: &impl Fn([u64; 16]) -> [u64; 16], x: [u64; 16], directions: &[[u64; 16]]) -> [u64; 16] {
+    let mut sum = [0u64; 16];
+    for subset in 0..1usize << directions.len() {
+        let mut point = x;
+        for (i, direction) in directions.iter().enumerate() {
+            if subset >> i & 1 == 1 {
+                for (word, d) in point.iter_mut().zip(direction) {
+                    *word ^= d;
+                }
+            }
+        }
+        for (word, y) in sum.iter_mut().zip(g(point)) {
+            *word ^= y;
+        }
+    }
+    sum
+}
+
+/// Lower bound on the algebraic degree of each output word of `g`, and of the
+/// XOR of all output words, from `trials` random cubes of each dimension up
+/// to `max_degree`.
+fn degree_lower_bounds(g: impl Fn([u64; 16]) -> [u64; 16], max_degree: usize, trials: usize) -> ([usize; 16], usize) {
+    use rand_chacha::rand_core::{RngCore, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    let mut rng = ChaCha8Rng::seed_from_u64(0xDE6);
+    let mut random_state = || -> [u64; 16] { core::array::from_fn(|_| rng.next_u64()) };
+    let (mut words, mut total) = ([0usize; 16], 0usize);
+    for degree in 1..=max_degree {
+        for _ in 0..trials {
+            let x = random_state();
+            let directions: Vec<[u64; 16]> = (0..degree).map(|_| random_state()).collect();
+            let sum = cube_sum(&g, x, &directions);
+            for (bound, word) in words.iter_mut().zip(sum) {
+                if word != 0 {
+                    *bound = degree;
+                }
+            }
+            if sum.iter().fold(0, |acc, word| acc ^ word) != 0 {
+                total = degree;
+            }
+        }
+    }
+    (words, total)
+}
+
+#[test]
+fn auxcrypt_algebraic_degree() {
+    use crate::variant::{AuxCrypt1024, AuxCrypt1024V2, AuxCrypt1024V3, AuxCrypt512, AuxCrypt512V2, AuxCrypt512V3};
+    use crate::AuxCryptPermutation;
+
+    fn permute<V: crate::variant::AuxCryptVariant>(mut state: [u64; 16]) -> [u64; 16] {
+        AuxCryptPermutation::<V>::permute(&mut state);
+        state
+    }
+
+    // Revisions 1 and 2 are affine: every second derivative vanishes.
+    assert_eq!(degree_lower_bounds(permute::<AuxCrypt512>, 3, 4), ([1; 16], 1));
+    assert_eq!(degree_lower_bounds(permute::<AuxCrypt1024>, 3, 4), ([1; 16], 1));
+    assert_eq!(degree_lower_bounds(permute::<AuxCrypt512V2>, 3, 4), ([1; 16], 1));
+    assert_eq!(degree_lower_bounds(permute::<AuxCrypt1024V2>, 3, 4), ([1; 16], 1));
+
+    // Revision 3 reaches the highest tested degree in every output word, and
+    // in the XOR of all words, which revisions 1 and 2 keep affine.
+    const MAX_DEGREE: usize = 8;
+    for bounds in [
+        degree_lower_bounds(permute::<AuxCrypt512V3>, MAX_DEGREE, 2),
+        degree_lower_bounds(permute::<AuxCrypt1024V3>, MAX_DEGREE, 2),
+    ] {
+        assert_eq!(bounds, ([MAX_DEGREE; 16], MAX_DEGREE), "Revision 3 must not be affine or low-degree");
+    }
+}
+
+#[test]
+fn auxcrypt_revision_3_vectors_and_inverse() {
+    use crate::variant::{AuxCrypt512V2, AuxCrypt512V3};
+    use crate::AuxCryptPermutation;
+
+    let words: [u64; 16] = core::array::from_fn(|i| (i as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    let mut v2 = words;
+    let mut v3 = words;
+    AuxCryptPermutation::<AuxCrypt512V2>::permute(&mut v2);
+    AuxCryptPermutation::<AuxCrypt512V3>::permute(&mut v3);
+    assert_ne!(v2, v3, "Revisions should be different permutations");
+    assert_eq!((v3[0], v3[15]), (0x3af4_49e2_8fd4_2a54, 0x2459_4cc1_b5af_1025));
+    AuxCryptPermutation::<AuxCrypt512V3>::permute_inverse(&mut v3);
+    assert_eq!(v3, words, "Revision 3 inverse should round-trip");
+
+    // The XOR of all words is no longer preserved up to a constant.
+    let xor_all = |state: &[u64; 16]| state.iter().fold(0, |acc, word| acc ^ word);
+    let shifted: [u64; 16] = core::array::from_fn(|i| words[i] ^ (i as u64 + 1));
+    let mut p = words;
+    let mut q = shifted;
+    AuxCryptPermutation::<AuxCrypt512V3>::permute(&mut p);
+    AuxCryptPermutation::<AuxCrypt512V3>::permute(&mut q);
+    assert_ne!(xor_all(&p) ^ xor_all(&q), xor_all(&words) ^ xor_all(&shifted));
+}
+
+#[test]
+fn auxcrypt_parallel_keystream_matches_serial() {
+    use crate::backends::soft;
+    use crate::stream::AuxCryptCore;
+    use crate::variant::AuxCrypt512;
+    use crate::test_support::assert_par_blocks_match_serial as check;
+
+    let key = [0x77; 64].into();
+    let nonce = [0x88; 64].into();
+
+    // 7 blocks: one 4-block parallel batch plus a 3-block tail.
+    let mut bulk = vec![0u8; 7 * 128];
+    AuxCrypt512Stream::new(&key, &nonce).apply_keystream(&mut bulk);
+    let mut serial = vec![0u8; 7 * 128];
+    let mut cipher = AuxCrypt512Stream::new(&key, &nonce);
+    for block in serial.chunks_mut(128) {
+        cipher.apply_keystream(block);
+    }
+    assert_eq!(bulk, serial, "Parallel and serial keystreams should match");
+
+    let mut par_core = AuxCryptCore::<AuxCrypt512>::new(&key, &nonce);
+    let mut serial_core = AuxCryptCore::<AuxCrypt512>::new(&key, &nonce);
+    check(&mut soft::Backend(&mut par_core), &mut soft::Backend(&mut serial_core));
+    assert_eq!(par_core.counter, 4, "Counter should advance by the number of blocks");
+
+    #[cfg(all(feature = "auxcrypt_simd", target_arch = "x86_64"))]
+    if crate::Backend::Simd.is_available() {
+        use crate::backends::simd;
+        check(&mut simd::Backend(&mut par_core), &mut soft::Backend(&mut serial_core));
+    }
+}
+
+#[test]
+fn auxcrypt_backends_differential() {
+    use crate::backends::Backend;
+    use crate::stream::AuxCryptCore;
+    use crate::variant::{
+        AuxCrypt1024, AuxCrypt1024V2, AuxCrypt1024V3, AuxCrypt512, AuxCrypt512V2, AuxCrypt512V3, AuxCryptVariant,
+    };
+    use cipher::{Iv, Key, StreamCipherSeekCore};
+    use rand_chacha::rand_core::{RngCore, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    // Every available backend must agree with Soft on random states, keys,
+    // nonces and block positions: forward, inverse, serial and parallel.
+    fn check<V: AuxCryptVariant>(rng: &mut ChaCha8Rng) {
+        for _ in 0..16 {
+            let words: [u64; 16] = core::array::from_fn(|_| rng.next_u64());
+            let mut forward = words;
+            Backend::Soft.permute::<V>(&mut forward);
+            let mut inverse = words;
+            Backend::Soft.permute_inverse::<V>(&mut inverse);
+
+            let mut key = Key::<AuxCryptCore<V>>::default();
+            let mut nonce = Iv::<AuxCryptCore<V>>::default();
+            rng.fill_bytes(&mut key);
+            rng.fill_bytes(&mut nonce);
+            let pos = u128::from(rng.next_u64() >> 1);
+            // 7 blocks: one 4-block parallel batch plus a 3-block tail.
+            let keystream = |backend: Backend| {
+                let mut out = vec![0u8; 7 * 128];
+                let mut core = AuxCryptCore::<V>::new(&key, &nonce);
+                core.set_block_pos(pos.try_into().unwrap());
+                core.process_with(backend, KeystreamInto(&mut out));
+                assert_eq!(u128::from(core.get_block_pos()), pos + 7);
+                out
+            };
+            let expected = keystream(Backend::Soft);
+
+            for backend in Backend::ALL.into_iter().filter(|backend| backend.is_available()) {
+                let mut state = words;
+                backend.permute::<V>(&mut state);
+                assert_eq!(state, forward, "{backend:?} permutation must match Soft");
+                let mut state = words;
+                backend.permute_inverse::<V>(&mut state);
+                assert_eq!(state, inverse, "{backend:?} inverse must match Soft");
+                assert_eq!(keystream(backend), expected, "{backend:?} keystream must match Soft");
+            }
+        }
+    }
+
+    let mut rng = ChaCha8Rng::seed_from_u64(0xA0C5);
+    check::<AuxCrypt512>(&mut rng);
+    check::<AuxCrypt1024>(&mut rng);
+    check::<AuxCrypt512V2>(&mut rng);
+    check::<AuxCrypt1024V2>(&mut rng);
+    check::<AuxCrypt512V3>(&mut rng);
+    check::<AuxCrypt1024V3>(&mut rng);
+}
+
+#[test]
+fn auxcrypt_backend_selection() {
+    use crate::Backend;
+
+    assert!(Backend::Soft.is_available());
+    assert!(Backend::active().is_available());
+    if !Backend::Simd.is_available() {
+        assert!(Backend::Simd.select().is_err(), "Unavailable backend must be rejected");
+    }
+}
+
+#[test]
+fn auxcrypt_core_is_wiped_on_drop() {
+    use crate::stream::AuxCryptCore;
+    use crate::variant::AuxCrypt512;
+    use zeroize::ZeroizeOnDrop;
+
+    let mut core = AuxCryptCore::<AuxCrypt512>::new(&[0x5A; 64].into(), &[0xA5; 64].into());
+    cipher::StreamCipherCore::apply_keystream_blocks(&mut core, &mut [[0u8; 128].into(); 5]);
+    assert!(core.state.iter().any(|&w| w != 0));
+    assert!(wiped_on_drop(core), "Stream core state should be wiped");
+
+    fn assert_zeroize_on_drop<T: ZeroizeOnDrop>() {}
+    assert_zeroize_on_drop::<AuxCrypt512Stream>();
+    assert_zeroize_on_drop::<AuxCrypt1024Stream>();
+}
@@ -4,6 +4,30 @@
 //======================================================================
 
 use cipher::consts::{U128, U64};
+use core::marker::PhantomData;
+
+/// Revision of the AuxCrypt permutation.
+///
+/// Every revision is a different permutation, so each variant pins one. The
+/// built-in variants keep [`Revision::V1`] so existing outputs stay reproducible.
+///
+/// In [`Revision::V1`] and [`Revision::V2`] the function `f` is affine over
+/// GF(2), so the whole permutation is affine and the keystream is a linear
+/// function of key and nonce. Use [`Revision::V3`] for anything new.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Revision {
+    /// Iota constants `0, 1, 2, ...` XORed into word 0 ([`RC`](crate::consts::RC)).
+    V1,
+    /// LFSR-derived 64-bit constants XORed into all 16 words
+    /// ([`LFSR_RC`](crate::consts::LFSR_RC)).
+    V2,
+    /// The [`Revision::V2`] constants with a non-linear round:
+    /// - `f(x) = (¬x) ⊕ (x <<< R_A) ⊕ (x <<< R_B) ⊕ ((x <<< R_C) ∧ (x <<< R_D))`;
+    /// - after each Lai-Massey step the first word of the pair goes through the
+    ///   orthomorphism `σ(hi ‖ lo) = lo ‖ (hi ⊕ lo)` (32-bit halves), so the XOR
+    ///   of the pair, and of the whole state, is no longer invariant.
+    V3,
+}
 
 /// A trait that defines the parameters for a specific AuxCrypt variant.
 pub trait AuxCryptVariant: Sized + Clone + Send + Sync + 'static {
@@ -15,8 +39,19 @@ pub trait AuxCryptVariant: Sized + Clone + Send + Sync + 'static {
 
     /// Number of permutation rounds.
     const ROUNDS: usize;
+
+    /// Round-constant revision of the permutation.
+    const REVISION: Revision = Revision::V1;
 }
 
+/// Marker for variants whose permutation is not affine over GF(2).
+///
+/// An affine permutation gives a keystream that is a linear function of key
+/// and nonce, so constructions that are only as strong as their strongest
+/// permutation require this bound. Only implement it for a variant whose
+/// [`REVISION`](AuxCryptVariant::REVISION) is [`Revision::V3`] or later.
+pub trait NonAffineVariant: AuxCryptVariant {}
+
 /// AuxCrypt variant with a 512-bit key.
 #[derive(Clone)]
 pub struct AuxCrypt512;
@@ -33,4 +68,50 @@ impl AuxCryptVariant for AuxCrypt1024 {
     type KeySize = U128;
     type NonceSize = U64;
     const ROUNDS: usize = 20;
-}
\ No newline at end of file
+}
+
+/// Variant `V` with the [`Revision::V2`] (LFSR) round constants.
+pub struct Rev2<V: AuxCryptVariant>(PhantomData<V>);
+
+impl<V: AuxCryptVariant> Clone for Rev2<V> {
+    fn clone(&self) -> Self {
+        Rev2(PhantomData)
+    }
+}
+
+impl<V: AuxCryptVariant> AuxCryptVariant for Rev2<V> {
+    type KeySize = V::KeySize;
+    type NonceSize = V::NonceSize;
+    const ROUNDS: usize = V::ROUNDS;
+    const REVISION: Revision = Revision::V2;
+}
+
+/// [`AuxCrypt512`] with LFSR round constants.
+pub type AuxCrypt512V2 = Rev2<AuxCrypt512>;
+
+/// [`AuxCrypt1024`] with LFSR round constants.
+pub type AuxCrypt1024V2 = Rev2<AuxCrypt1024>;
+
+/// Variant `V` with the [`Revision::V3`] (non-linear) round.
+pub struct Rev3<V: AuxCryptVariant>(PhantomData<V>);
+
+impl<V: AuxCryptVariant> Clone for Rev3<V> {
+    fn clone(&self) -> Self {
+        Rev3(PhantomData)
+    }
+}
+
+impl<V: AuxCryptVariant> AuxCryptVariant for Rev3<V> {
+    type KeySize = V::KeySize;
+    type NonceSize = V::NonceSize;
+    const ROUNDS: usize = V::ROUNDS;
+    const REVISION: Revision = Revision::V3;
+}
+
+impl<V: AuxCryptVariant> NonAffineVariant for Rev3<V> {}
+
+/// [`AuxCrypt512`] with the non-linear round.
+pub type AuxCrypt512V3 = Rev3<AuxCrypt512>;
+
+/// [`AuxCrypt1024`] with the non-linear round.
+pub type AuxCrypt1024V3 = Rev3<AuxCrypt1024>;
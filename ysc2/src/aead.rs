@@ -8,7 +8,8 @@
 //======================================================================
 
 use crate::backends;
-use crate::consts::{RATE_BYTES, STATE_WORDS};
+use crate::consts::{MIN_KEY_BYTES, RATE_BYTES, STATE_WORDS};
+use crate::stream::{absorb_length_prefixed, init_iv, INIT_PHASE_AEAD_NONCE, INIT_PHASE_KEY, MODE_AEAD_VARIABLE};
 use crate::variant::Ysc2Variant;
 use core::marker::PhantomData;
 use aead::{
@@ -16,6 +17,7 @@ use aead::{
     generic_array::GenericArray,
     AeadCore, AeadInPlace, Key, KeyInit, KeySizeUser, Nonce, Tag,
 };
+use cipher::InvalidLength;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
 /// YSC2-X AEAD Cipher.
@@ -25,6 +27,155 @@ pub struct Ysc2Aead<V: Ysc2Variant> {
     _variant: PhantomData<V>,
 }
 
+impl<V: Ysc2Variant> Ysc2Aead<V> {
+    /// Creates an AEAD instance from a key of any length from [`MIN_KEY_BYTES`] up.
+    ///
+    /// The key is absorbed with its length under a dedicated mode, so this
+    /// never agrees with [`KeyInit::new`], which stays the path for
+    /// `V::KEY_SIZE`-byte keys.
+    ///
+    /// # Errors
+    /// Returns [`InvalidLength`] if `key` is shorter than [`MIN_KEY_BYTES`].
+    pub fn new_variable(key: &[u8]) -> Result<Self, InvalidLength> {
+        if key.len() < MIN_KEY_BYTES {
+            return Err(InvalidLength);
+        }
+        let mut state = [0u64; STATE_WORDS];
+        state[8..].copy_from_slice(&init_iv::<V>(MODE_AEAD_VARIABLE));
+        absorb_length_prefixed::<V>(&mut state, INIT_PHASE_KEY, key);
+        absorb_padded_data::<V>(&mut state, V::AEAD_DOMAIN.as_bytes());
+
+        let aead = Self { initial_state: state, _variant: PhantomData };
+        state.zeroize();
+        Ok(aead)
+    }
+
+    /// Like [`AeadInPlace::encrypt_in_place_detached`], but with a nonce of any
+    /// length (e.g. 12 or 16 bytes).
+    ///
+    /// The nonce is absorbed with its length under its own phase value, so
+    /// distinct nonces never collide. This never agrees with the trait
+    /// method, even for a `V::NONCE_SIZE`-byte nonce.
+    pub fn encrypt_in_place_detached_with_nonce(
+        &self,
+        nonce: &[u8],
+        associated_data: &[u8],
+        buffer: &mut [u8],
+    ) -> aead::Result<Tag<Self>> {
+        encrypt_detached::<V>(self.variable_nonce_state(nonce), associated_data, buffer)
+    }
+
+    /// Like [`AeadInPlace::decrypt_in_place_detached`], but with a nonce of any
+    /// length (see [`encrypt_in_place_detached_with_nonce`](Self::encrypt_in_place_detached_with_nonce)).
+    pub fn decrypt_in_place_detached_with_nonce(
+        &self,
+        nonce: &[u8],
+        associated_data: &[u8],
+        buffer: &mut [u8],
+        tag: &Tag<Self>,
+    ) -> aead::Result<()> {
+        decrypt_detached::<V>(self.variable_nonce_state(nonce), associated_data, buffer, tag)
+    }
+
+    /// 트레잇 메서드의 고정 길이 Nonce 흡수 (기존 출력 유지)
+    fn fixed_nonce_state(&self, nonce: &Nonce<Self>) -> [u64; STATE_WORDS] {
+        let mut state = self.initial_state;
+        absorb_padded_data::<V>(&mut state, nonce);
+        state
+    }
+
+    /// 가변 길이 Nonce 흡수: 길이를 붙여 전용 단계 값으로 흡수합니다.
+    fn variable_nonce_state(&self, nonce: &[u8]) -> [u64; STATE_WORDS] {
+        let mut state = self.initial_state;
+        absorb_length_prefixed::<V>(&mut state, INIT_PHASE_AEAD_NONCE, nonce);
+        state
+    }
+}
+
+/// Nonce를 흡수한 상태에서 연관 데이터를 흡수하고 `buffer`를 암호화한 뒤 태그를 만듭니다.
+fn encrypt_detached<V: Ysc2Variant>(
+    mut state: [u64; STATE_WORDS],
+    associated_data: &[u8],
+    buffer: &mut [u8],
+) -> aead::Result<Tag<Ysc2Aead<V>>> {
+    absorb_padded_data::<V>(&mut state, associated_data);
+
+    // Process plaintext: Squeeze, encrypt, then absorb ciphertext.
+    for chunk in buffer.chunks_mut(RATE_BYTES) {
+        backends::permutation::<V>(&mut state);
+        
+        let mut keystream_block = [0u8; RATE_BYTES];
+        for (i, ks_chunk) in keystream_block.chunks_exact_mut(8).enumerate() {
+            ks_chunk.copy_from_slice(&state[i].to_le_bytes());
+        }
+
+        // Encrypt in-place
+        for (i, byte) in chunk.iter_mut().enumerate() {
+            *byte ^= keystream_block[i];
+        }
+        
+        // Absorb the resulting ciphertext.
+        absorb_padded_data::<V>(&mut state, chunk);
+        keystream_block.zeroize();
+    }
+
+    // Finalize and generate the tag.
+    backends::permutation::<V>(&mut state);
+    let mut tag = [0u8; 16];
+    tag[..8].copy_from_slice(&state[0].to_le_bytes());
+    tag[8..].copy_from_slice(&state[1].to_le_bytes());
+    state.zeroize();
+
+    Ok(GenericArray::clone_from_slice(&tag))
+}
+
+/// Nonce를 흡수한 상태에서 연관 데이터를 흡수하고 `buffer`를 복호화한 뒤 태그를 검증합니다.
+fn decrypt_detached<V: Ysc2Variant>(
+    mut state: [u64; STATE_WORDS],
+    associated_data: &[u8],
+    buffer: &mut [u8],
+    tag: &Tag<Ysc2Aead<V>>,
+) -> aead::Result<()> {
+    absorb_padded_data::<V>(&mut state, associated_data);
+
+    // Process ciphertext: Absorb ciphertext, then squeeze, then decrypt.
+    for chunk in buffer.chunks_mut(RATE_BYTES) {
+        backends::permutation::<V>(&mut state);
+        
+        let mut keystream_block = [0u8; RATE_BYTES];
+        for (i, ks_chunk) in keystream_block.chunks_exact_mut(8).enumerate() {
+            ks_chunk.copy_from_slice(&state[i].to_le_bytes());
+        }
+
+        // Absorb the ciphertext before decryption.
+        absorb_padded_data::<V>(&mut state, chunk);
+
+        // Decrypt in-place
+        for (i, byte) in chunk.iter_mut().enumerate() {
+            *byte ^= keystream_block[i];
+        }
+        keystream_block.zeroize();
+    }
+
+    // Finalize and generate the tag for verification.
+    backends::permutation::<V>(&mut state);
+    let mut calculated_tag = [0u8; 16];
+    calculated_tag[..8].copy_from_slice(&state[0].to_le_bytes());
+    calculated_tag[8..].copy_from_slice(&state[1].to_le_bytes());
+    state.zeroize();
+
+    // Constant-time tag comparison.
+    let valid = ct_compare(&calculated_tag, tag.as_slice());
+    calculated_tag.zeroize();
+    if valid {
+        Ok(())
+    } else {
+        // On failure, zero out the decrypted (but unauthenticated) buffer.
+        buffer.iter_mut().for_each(|b| *b = 0);
+        Err(aead::Error)
+    }
+}
+
 impl<V: Ysc2Variant> KeySizeUser for Ysc2Aead<V> {
     type KeySize = V::KeySize;
 }
@@ -42,7 +193,9 @@ impl<V: Ysc2Variant> KeyInit for Ysc2Aead<V> {
         // 2. Absorb the AEAD domain separator.
         absorb_padded_data::<V>(&mut state, V::AEAD_DOMAIN.as_bytes());
 
-        Self { initial_state: state, _variant: PhantomData }
+        let aead = Self { initial_state: state, _variant: PhantomData };
+        state.zeroize();
+        aead
     }
 }
 
@@ -59,36 +212,7 @@ impl<V: Ysc2Variant> AeadInPlace for Ysc2Aead<V> {
         associated_data: &[u8],
         buffer: &mut [u8],
     ) -> aead::Result<Tag<Self>> {
-        let mut state = self.initial_state;
-
-        absorb_padded_data::<V>(&mut state, nonce);
-        absorb_padded_data::<V>(&mut state, associated_data);
-
-        // Process plaintext: Squeeze, encrypt, then absorb ciphertext.
-        for chunk in buffer.chunks_mut(RATE_BYTES) {
-            backends::permutation::<V>(&mut state);
-            
-            let mut keystream_block = [0u8; RATE_BYTES];
-            for (i, ks_chunk) in keystream_block.chunks_exact_mut(8).enumerate() {
-                ks_chunk.copy_from_slice(&state[i].to_le_bytes());
-            }
-
-            // Encrypt in-place
-            for (i, byte) in chunk.iter_mut().enumerate() {
-                *byte ^= keystream_block[i];
-            }
-            
-            // Absorb the resulting ciphertext.
-            absorb_padded_data::<V>(&mut state, chunk);
-        }
-
-        // Finalize and generate the tag.
-        backends::permutation::<V>(&mut state);
-        let mut tag = [0u8; 16];
-        tag[..8].copy_from_slice(&state[0].to_le_bytes());
-        tag[8..].copy_from_slice(&state[1].to_le_bytes());
-
-        Ok(GenericArray::clone_from_slice(&tag))
+        encrypt_detached::<V>(self.fixed_nonce_state(nonce), associated_data, buffer)
     }
 
     fn decrypt_in_place_detached(
@@ -98,43 +222,7 @@ impl<V: Ysc2Variant> AeadInPlace for Ysc2Aead<V> {
         buffer: &mut [u8],
         tag: &Tag<Self>,
     ) -> aead::Result<()> {
-        let mut state = self.initial_state;
-
-        absorb_padded_data::<V>(&mut state, nonce);
-        absorb_padded_data::<V>(&mut state, associated_data);
-        
-        // Process ciphertext: Absorb ciphertext, then squeeze, then decrypt.
-        for chunk in buffer.chunks_mut(RATE_BYTES) {
-            backends::permutation::<V>(&mut state);
-            
-            let mut keystream_block = [0u8; RATE_BYTES];
-            for (i, ks_chunk) in keystream_block.chunks_exact_mut(8).enumerate() {
-                ks_chunk.copy_from_slice(&state[i].to_le_bytes());
-            }
-
-            // Absorb the ciphertext before decryption.
-            absorb_padded_data::<V>(&mut state, chunk);
-
-            // Decrypt in-place
-            for (i, byte) in chunk.iter_mut().enumerate() {
-                *byte ^= keystream_block[i];
-            }
-        }
-
-        // Finalize and generate the tag for verification.
-        backends::permutation::<V>(&mut state);
-        let mut calculated_tag = [0u8; 16];
-        calculated_tag[..8].copy_from_slice(&state[0].to_le_bytes());
-        calculated_tag[8..].copy_from_slice(&state[1].to_le_bytes());
-
-        // Constant-time tag comparison.
-        if ct_compare(&calculated_tag, tag.as_slice()) {
-            Ok(())
-        } else {
-            // On failure, zero out the decrypted (but unauthenticated) buffer.
-            buffer.iter_mut().for_each(|b| *b = 0);
-            Err(aead::Error)
-        }
+        decrypt_detached::<V>(self.fixed_nonce_state(nonce), associated_data, buffer, tag)
     }
 }
 
@@ -163,7 +251,7 @@ fn absorb_padded_data<V: Ysc2Variant>(state: &mut [u64; STATE_WORDS], data: &[u8
 
     // If data length is a multiple of RATE_BYTES or is empty,
     // an additional block with padding must be absorbed.
-    if data.is_empty() || data.len() % RATE_BYTES == 0 {
+    if data.is_empty() || data.len().is_multiple_of(RATE_BYTES) {
         let mut padding_block = [0u8; RATE_BYTES];
         padding_block[0] = 0x01;
         for i in 0..8 {
@@ -1,11 +1,185 @@
-use cfg_if::cfg_if;
+//======================================================================
+// src/backends/mod.rs
+// 순열 백엔드 모음과 런타임 백엔드 선택 로직
+//======================================================================
 
-cfg_if! {
-    if #[cfg(feature = "ysc2_simd")] {
-        mod simd;
-        pub(crate) use simd::*;
-    } else {
-        mod soft;
-        pub(crate) use soft::*;
+use core::fmt;
+use core::sync::atomic::{AtomicU8, Ordering};
+use crate::consts::{RATE_BYTES, STATE_WORDS};
+use crate::stream::StreamRevision;
+use crate::variant::Ysc2Variant;
+use zeroize::Zeroize;
+
+pub(crate) mod soft;
+
+#[cfg(all(feature = "ysc2_simd", target_arch = "x86_64"))]
+pub(crate) mod simd;
+
+/// Number of independent states permuted together by [`Backend::permute_x4`].
+#[cfg(feature = "ysc2x")]
+pub(crate) const LANES: usize = 4;
+
+/// Implementation used to evaluate the YSC2 permutation.
+///
+/// The `soft` backend is always available. The `simd` backend is compiled in
+/// with the `ysc2_simd` feature and is only usable when the running CPU
+/// supports it; by default the fastest available backend is picked on first use.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Backend {
+    /// Portable scalar implementation.
+    Soft,
+    /// AVX2 implementation (x86_64 only).
+    Simd,
+}
+
+/// Error returned when selecting a backend that is not available.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BackendUnavailable(pub Backend);
+
+impl fmt::Display for BackendUnavailable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the {:?} backend is not available on this build or CPU", self.0)
+    }
+}
+
+impl std::error::Error for BackendUnavailable {}
+
+// 0 = 아직 감지되지 않음, 그 외에는 `Backend::to_tag`의 값
+static SELECTED: AtomicU8 = AtomicU8::new(0);
+
+impl Backend {
+    /// Every backend, whether or not it is available.
+    pub const ALL: [Backend; 2] = [Backend::Soft, Backend::Simd];
+
+    const fn to_tag(self) -> u8 {
+        match self {
+            Backend::Soft => 1,
+            Backend::Simd => 2,
+        }
+    }
+
+    const fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            1 => Some(Backend::Soft),
+            2 => Some(Backend::Simd),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if this backend is compiled in and supported by the CPU.
+    pub fn is_available(self) -> bool {
+        match self {
+            Backend::Soft => true,
+            #[cfg(all(feature = "ysc2_simd", target_arch = "x86_64"))]
+            Backend::Simd => simd::is_available(),
+            #[cfg(not(all(feature = "ysc2_simd", target_arch = "x86_64")))]
+            Backend::Simd => false,
+        }
+    }
+
+    /// Returns the fastest backend available on the running CPU.
+    pub fn detect() -> Self {
+        if Backend::Simd.is_available() {
+            Backend::Simd
+        } else {
+            Backend::Soft
+        }
+    }
+
+    /// Returns the backend currently used by all YSC2 constructions.
+    pub fn active() -> Self {
+        match Self::from_tag(SELECTED.load(Ordering::Relaxed)) {
+            Some(backend) => backend,
+            None => {
+                let backend = Self::detect();
+                SELECTED.store(backend.to_tag(), Ordering::Relaxed);
+                backend
+            }
+        }
+    }
+
+    /// Forces every YSC2 construction in this process to use `self`.
+    ///
+    /// All backends compute the same permutation, so switching at any point
+    /// only affects speed, never outputs.
+    pub fn select(self) -> Result<(), BackendUnavailable> {
+        if !self.is_available() {
+            return Err(BackendUnavailable(self));
+        }
+        SELECTED.store(self.to_tag(), Ordering::Relaxed);
+        Ok(())
+    }
+
+    // 아래 메서드는 선택 상태와 무관하게 이 백엔드로 계산합니다.
+    // 사용할 수 없는 SIMD 백엔드는 soft 구현으로 대체됩니다.
+
+    #[inline]
+    pub(crate) fn permute<V: Ysc2Variant>(self, state: &mut [u64; 16]) {
+        match self {
+            #[cfg(all(feature = "ysc2_simd", target_arch = "x86_64"))]
+            Backend::Simd => simd::permutation::<V>(state),
+            _ => soft::permutation::<V>(state),
+        }
+    }
+
+    #[cfg(feature = "ysc2x")]
+    #[inline]
+    pub(crate) fn permute_x4<V: Ysc2Variant>(self, states: &mut [[u64; 16]; LANES]) {
+        match self {
+            #[cfg(all(feature = "ysc2_simd", target_arch = "x86_64"))]
+            Backend::Simd => simd::permutation_x4::<V>(states),
+            _ => soft::permutation_par::<V, LANES>(states),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn permute_inverse<V: Ysc2Variant>(self, state: &mut [u64; 16]) {
+        match self {
+            #[cfg(all(feature = "ysc2_simd", target_arch = "x86_64"))]
+            Backend::Simd => simd::permutation_inverse::<V>(state),
+            _ => soft::permutation_inverse::<V>(state),
+        }
+    }
+}
+
+/// The state permutation, evaluated with the active backend.
+#[inline]
+pub(crate) fn permutation<V: Ysc2Variant>(state: &mut [u64; 16]) {
+    Backend::active().permute::<V>(state)
+}
+
+/// The inverse state permutation, evaluated with the active backend.
+#[inline]
+pub(crate) fn permutation_inverse<V: Ysc2Variant>(state: &mut [u64; 16]) {
+    Backend::active().permute_inverse::<V>(state)
+}
+
+/// 카운터 더하기: 스폰지 리비전은 카운터를 rate의 모든 워드에, 레거시는 워드 0에만 XOR합니다.
+/// 순열은 워드 `i`와 `i + 8`만 섞으므로 rate의 모든 워드가 블록마다 달라지려면 전부에 넣어야 합니다.
+#[inline(always)]
+pub(crate) fn add_counter<R: StreamRevision>(state: &mut [u64; STATE_WORDS], counter: u64) {
+    let words = if R::RATE_OUTPUT { RATE_BYTES / 8 } else { 1 };
+    for word in state[..words].iter_mut() {
+        *word ^= counter;
+    }
+}
+
+/// 한 번 순열을 거친 카운터 블록들을 키스트림 블록으로 만듭니다.
+/// 스폰지 리비전은 `permute`로 한 번 더 순열을 적용해 `rate(P(x)) || rate(P(P(x)))`를,
+/// 레거시는 `P(x)`를 그대로 내보냅니다.
+#[inline(always)]
+pub(crate) fn squeeze_blocks<R: StreamRevision, const N: usize>(
+    states: &mut [[u64; STATE_WORDS]; N],
+    permute: impl FnOnce(&mut [[u64; STATE_WORDS]; N]),
+) {
+    if R::RATE_OUTPUT {
+        const RATE_WORDS: usize = RATE_BYTES / 8;
+        let mut first: [[u64; RATE_WORDS]; N] = core::array::from_fn(|b| states[b][..RATE_WORDS].try_into().unwrap());
+        permute(states);
+        for (state, rate) in states.iter_mut().zip(first.iter()) {
+            state.copy_within(..RATE_WORDS, RATE_WORDS);
+            state[..RATE_WORDS].copy_from_slice(rate);
+        }
+        first.zeroize();
     }
-}
\ No newline at end of file
+}
@@ -1,85 +1,284 @@
+//======================================================================
+// src/backends/simd.rs
+// AVX2 백엔드. 상태를 4개의 256비트 벡터(행)로 보고 순열을 계산합니다.
+//======================================================================
 
-use crate::{stream::Ysc2StreamCore, variant::Ysc2Variant, consts::{ROT_A, ROT_B}};
-use cipher::{Block, BlockSizeUser, ParBlocksSizeUser, StreamBackend};
-use std::simd::{Simd, u64x4};
+use crate::{stream::{StreamRevision, Ysc2KeystreamCore}, variant::{Revision, Ysc2Variant}, consts::{ROT_A, ROT_B}};
+use cipher::{Block, BlockSizeUser, ParBlocks, ParBlocksSizeUser, StreamBackend};
+use zeroize::Zeroize;
+use core::arch::x86_64::*;
 use crate::consts::*;
 
+/// Returns `true` if the running CPU supports this backend.
+#[inline]
+pub(crate) fn is_available() -> bool {
+    std::is_x86_feature_detected!("avx2")
+}
+
+/// 64비트 레인 단위 왼쪽 회전
+#[inline]
+#[target_feature(enable = "avx2")]
+fn rotl<const L: i32, const R: i32>(x: __m256i) -> __m256i {
+    _mm256_or_si256(_mm256_slli_epi64::<L>(x), _mm256_srli_epi64::<R>(x))
+}
+
 /// 비선형 함수 g(x)의 벡터 버전
-#[inline(always)]
-fn g_vec(x: Simd<u64, 4>) -> Simd<u64, 4> {
-    // 각 u64 레인에 대해 비트 단위 회전을 수행합니다.
-    let rot_a = (x << Simd::splat(ROT_A as u64)) | (x >> Simd::splat(64u64 - ROT_A as u64));
-    let rot_b = (x << Simd::splat(ROT_B as u64)) | (x >> Simd::splat(64u64 - ROT_B as u64));
-    x ^ (rot_a & rot_b)
+#[inline]
+#[target_feature(enable = "avx2")]
+fn g_vec(x: __m256i) -> __m256i {
+    let rot_a = rotl::<{ ROT_A as i32 }, { 64 - ROT_A as i32 }>(x);
+    let rot_b = rotl::<{ ROT_B as i32 }, { 64 - ROT_B as i32 }>(x);
+    _mm256_xor_si256(x, _mm256_and_si256(rot_a, rot_b))
 }
 
-/// The portable SIMD backend for YSC2.
-pub struct Backend<'a, V: Ysc2Variant>(pub(crate) &'a mut Ysc2StreamCore<V>);
+/// 워드 순열 `P`의 한 행: 레인 c는 `(r + c) % 4`번째 행의 레인 c에서 옵니다.
+#[inline]
+#[target_feature(enable = "avx2")]
+fn diagonal(a: __m256i, b: __m256i, c: __m256i, d: __m256i) -> __m256i {
+    _mm256_blend_epi32::<0xF0>(
+        _mm256_blend_epi32::<0x0C>(a, b),
+        _mm256_blend_epi32::<0xC0>(c, d),
+    )
+}
+
+/// 행 배치에서의 라운드 상수 더하기: 행 k의 레인 c는 워드 `4k + c`입니다.
+#[inline]
+#[target_feature(enable = "avx2")]
+fn add_round_constant<V: Ysc2Variant>(s: &mut [__m256i; 4], round: usize, lfsr: &mut LfsrRoundConstants) {
+    match V::REVISION {
+        Revision::V1 => s[0] = _mm256_xor_si256(s[0], _mm256_set_epi64x(0, 0, 0, round_constant(round) as i64)),
+        Revision::V2 => {
+            let rc = lfsr.constants(round);
+            for (k, row) in s.iter_mut().enumerate() {
+                // SAFETY: `rc[4 * k..]` holds at least four words, i.e. one unaligned 256-bit vector.
+                let c = unsafe { _mm256_loadu_si256(rc[4 * k..].as_ptr() as *const __m256i) };
+                *row = _mm256_xor_si256(*row, c);
+            }
+        }
+    }
+}
+
+/// 전치 배치에서의 라운드 상수 더하기: 벡터 `w[j]`의 모든 레인이 워드 j입니다.
+#[inline]
+#[target_feature(enable = "avx2")]
+fn add_round_constant_x4<V: Ysc2Variant>(w: &mut [__m256i; 16], round: usize, lfsr: &mut LfsrRoundConstants) {
+    match V::REVISION {
+        Revision::V1 => w[0] = _mm256_xor_si256(w[0], _mm256_set1_epi64x(round_constant(round) as i64)),
+        Revision::V2 => {
+            for (word, rc) in w.iter_mut().zip(lfsr.constants(round)) {
+                *word = _mm256_xor_si256(*word, _mm256_set1_epi64x(rc as i64));
+            }
+        }
+    }
+}
 
-impl<'a, V: Ysc2Variant> BlockSizeUser for Backend<'a, V> {
+/// The AVX2 backend for YSC2.
+pub struct Backend<'a, V: Ysc2Variant, R: StreamRevision>(pub(crate) &'a mut Ysc2KeystreamCore<V, R>);
+
+impl<'a, V: Ysc2Variant, R: StreamRevision> BlockSizeUser for Backend<'a, V, R> {
     type BlockSize = cipher::consts::U128;
 }
 
-// Re-add ParBlocksSizeUser for compatibility with cipher v0.4.4
-impl<'a, V: Ysc2Variant> ParBlocksSizeUser for Backend<'a, V> {
-    type ParBlocksSize = cipher::consts::U128;
+/// 병렬로 생성하는 키스트림 블록 수 (256비트 벡터의 64비트 레인 수)
+pub(crate) const PAR_BLOCKS: usize = 4;
+
+impl<'a, V: Ysc2Variant, R: StreamRevision> ParBlocksSizeUser for Backend<'a, V, R> {
+    type ParBlocksSize = cipher::consts::U4;
 }
 
-impl<'a, V: Ysc2Variant> StreamBackend for Backend<'a, V> {
+impl<'a, V: Ysc2Variant, R: StreamRevision> StreamBackend for Backend<'a, V, R> {
     #[inline]
     fn gen_ks_block(&mut self, block: &mut Block<Self>) {
-        self.0.counter = self.0.counter.wrapping_add(1);
+        let counter = self.0.next_block_counter();
+
+        let mut states = [self.0.state];
+        super::add_counter::<R>(&mut states[0], counter);
+        permutation::<V>(&mut states[0]);
+        super::squeeze_blocks::<R, 1>(&mut states, |states| permutation::<V>(&mut states[0]));
+
+        for (chunk, word) in block.chunks_exact_mut(8).zip(states[0].iter()) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        states.zeroize();
+    }
 
-        let mut working_state = self.0.state;
-        working_state[0] ^= self.0.counter;
+    /// 카운터 블록 4개를 한 번에 계산합니다. 각 블록이 벡터의 한 레인을 차지합니다.
+    #[inline]
+    fn gen_par_ks_blocks(&mut self, blocks: &mut ParBlocks<Self>) {
+        let mut states = [self.0.state; PAR_BLOCKS];
+        for state in states.iter_mut() {
+            let counter = self.0.next_block_counter();
+            super::add_counter::<R>(state, counter);
+        }
 
-        permutation::<V>(&mut working_state);
+        permutation_x4::<V>(&mut states);
+        super::squeeze_blocks::<R, PAR_BLOCKS>(&mut states, permutation_x4::<V>);
 
-        for (i, chunk) in block.chunks_exact_mut(8).enumerate() {
-            chunk.copy_from_slice(&working_state[i].to_le_bytes());
+        for (block, state) in blocks.iter_mut().zip(states.iter()) {
+            for (chunk, word) in block.chunks_exact_mut(8).zip(state.iter()) {
+                chunk.copy_from_slice(&word.to_le_bytes());
+            }
         }
+        states.zeroize();
     }
 }
 
-/// The state permutation function using portable SIMD.
-#[inline(always)]
+/// The state permutation function using AVX2.
+///
+/// Falls back to the `soft` backend if the CPU lacks AVX2.
+#[inline]
 pub(crate) fn permutation<V: Ysc2Variant>(state: &mut [u64; 16]) {
-    let mut s: [u64x4; 4] = [
-        Simd::from_slice(&state[0..4]),
-        Simd::from_slice(&state[4..8]),
-        Simd::from_slice(&state[8..12]),
-        Simd::from_slice(&state[12..16]),
-    ];
+    if is_available() {
+        // SAFETY: AVX2 support was checked just above.
+        unsafe { permutation_avx2::<V>(state) }
+    } else {
+        super::soft::permutation::<V>(state)
+    }
+}
 
-    for r in 0..V::ROUNDS {
-        s[0][0] ^= RC[r];
+/// The inverse permutation using AVX2.
+///
+/// Falls back to the `soft` backend if the CPU lacks AVX2.
+#[inline]
+pub(crate) fn permutation_inverse<V: Ysc2Variant>(state: &mut [u64; 16]) {
+    if is_available() {
+        // SAFETY: AVX2 support was checked just above.
+        unsafe { permutation_inverse_avx2::<V>(state) }
+    } else {
+        super::soft::permutation_inverse::<V>(state)
+    }
+}
+
+/// Applies the permutation to four independent states at once.
+///
+/// Falls back to the `soft` backend if the CPU lacks AVX2.
+#[inline]
+pub(crate) fn permutation_x4<V: Ysc2Variant>(states: &mut [[u64; 16]; PAR_BLOCKS]) {
+    if is_available() {
+        // SAFETY: AVX2 support was checked just above.
+        unsafe { permutation_x4_avx2::<V>(states) }
+    } else {
+        super::soft::permutation_par::<V, PAR_BLOCKS>(states)
+    }
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn permutation_avx2<V: Ysc2Variant>(state: &mut [u64; 16]) {
+    let ptr = state.as_mut_ptr() as *mut __m256i;
+    // SAFETY: `state` is 128 bytes long, i.e. exactly four unaligned 256-bit vectors.
+    let mut s = unsafe {
+        [
+            _mm256_loadu_si256(ptr),
+            _mm256_loadu_si256(ptr.add(1)),
+            _mm256_loadu_si256(ptr.add(2)),
+            _mm256_loadu_si256(ptr.add(3)),
+        ]
+    };
+
+    let mut lfsr = LfsrRoundConstants::new();
+    for round in 0..V::ROUNDS {
+        add_round_constant::<V>(&mut s, round, &mut lfsr);
 
         let temp0 = g_vec(s[0]);
         let temp1 = g_vec(s[1]);
-        s[2] ^= temp0;
-        s[3] ^= temp1;
-        s[0] ^= s[2];
-        s[1] ^= s[3];
-
-        let mut temp_state = [0u64; 16];
-        s[0].copy_to_slice(&mut temp_state[0..4]);
-        s[1].copy_to_slice(&mut temp_state[4..8]);
-        s[2].copy_to_slice(&mut temp_state[8..12]);
-        s[3].copy_to_slice(&mut temp_state[12..16]);
-        
-        let mut new_state_array = [0u64; 16];
-        for i in 0..16 {
-            new_state_array[i] = temp_state[P[i]];
-        }
+        s[2] = _mm256_xor_si256(s[2], temp0);
+        s[3] = _mm256_xor_si256(s[3], temp1);
+        s[0] = _mm256_xor_si256(s[0], s[2]);
+        s[1] = _mm256_xor_si256(s[1], s[3]);
+
+        s = [
+            diagonal(s[0], s[1], s[2], s[3]),
+            diagonal(s[1], s[2], s[3], s[0]),
+            diagonal(s[2], s[3], s[0], s[1]),
+            diagonal(s[3], s[0], s[1], s[2]),
+        ];
+    }
+
+    // SAFETY: see the loads above.
+    unsafe {
+        _mm256_storeu_si256(ptr, s[0]);
+        _mm256_storeu_si256(ptr.add(1), s[1]);
+        _mm256_storeu_si256(ptr.add(2), s[2]);
+        _mm256_storeu_si256(ptr.add(3), s[3]);
+    }
+    s.zeroize();
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn permutation_inverse_avx2<V: Ysc2Variant>(state: &mut [u64; 16]) {
+    let ptr = state.as_mut_ptr() as *mut __m256i;
+    // SAFETY: `state` is 128 bytes long, i.e. exactly four unaligned 256-bit vectors.
+    let mut s = unsafe {
+        [
+            _mm256_loadu_si256(ptr),
+            _mm256_loadu_si256(ptr.add(1)),
+            _mm256_loadu_si256(ptr.add(2)),
+            _mm256_loadu_si256(ptr.add(3)),
+        ]
+    };
 
-        s[0] = Simd::from_slice(&new_state_array[0..4]);
-        s[1] = Simd::from_slice(&new_state_array[4..8]);
-        s[2] = Simd::from_slice(&new_state_array[8..12]);
-        s[3] = Simd::from_slice(&new_state_array[12..16]);
+    let mut lfsr = LfsrRoundConstants::new();
+    for round in (0..V::ROUNDS).rev() {
+        // `P`의 역: 행 k의 레인 c는 `(k - c) % 4`번째 행의 레인 c로 돌아갑니다.
+        s = [
+            diagonal(s[0], s[3], s[2], s[1]),
+            diagonal(s[1], s[0], s[3], s[2]),
+            diagonal(s[2], s[1], s[0], s[3]),
+            diagonal(s[3], s[2], s[1], s[0]),
+        ];
+
+        s[0] = _mm256_xor_si256(s[0], s[2]);
+        s[1] = _mm256_xor_si256(s[1], s[3]);
+        s[2] = _mm256_xor_si256(s[2], g_vec(s[0]));
+        s[3] = _mm256_xor_si256(s[3], g_vec(s[1]));
+
+        add_round_constant::<V>(&mut s, round, &mut lfsr);
+    }
+
+    // SAFETY: see the loads above.
+    unsafe {
+        _mm256_storeu_si256(ptr, s[0]);
+        _mm256_storeu_si256(ptr.add(1), s[1]);
+        _mm256_storeu_si256(ptr.add(2), s[2]);
+        _mm256_storeu_si256(ptr.add(3), s[3]);
+    }
+    s.zeroize();
+}
+
+/// 전치(transposed) 배치: 벡터 `w[j]`의 레인 b는 b번째 상태의 워드 j입니다.
+/// 이 배치에서는 워드 순열 `P`가 벡터 이름 바꾸기에 불과합니다.
+#[target_feature(enable = "avx2")]
+unsafe fn permutation_x4_avx2<V: Ysc2Variant>(states: &mut [[u64; 16]; PAR_BLOCKS]) {
+    let mut w: [__m256i; 16] = core::array::from_fn(|j| {
+        _mm256_set_epi64x(
+            states[3][j] as i64,
+            states[2][j] as i64,
+            states[1][j] as i64,
+            states[0][j] as i64,
+        )
+    });
+
+    let mut lfsr = LfsrRoundConstants::new();
+    for round in 0..V::ROUNDS {
+        add_round_constant_x4::<V>(&mut w, round, &mut lfsr);
+        for i in 0..8 {
+            w[i + 8] = _mm256_xor_si256(w[i + 8], g_vec(w[i])); // R' = R ^ g(L)
+        }
+        for i in 0..8 {
+            w[i] = _mm256_xor_si256(w[i], w[i + 8]); // L' = L ^ R'
+        }
+        w = core::array::from_fn(|i| w[P[i]]);
     }
 
-    s[0].copy_to_slice(&mut state[0..4]);
-    s[1].copy_to_slice(&mut state[4..8]);
-    s[2].copy_to_slice(&mut state[8..12]);
-    s[3].copy_to_slice(&mut state[12..16]);
+    let mut lanes = [0u64; PAR_BLOCKS];
+    for (j, word) in w.iter().enumerate() {
+        // SAFETY: `lanes` is exactly 256 bits long.
+        unsafe { _mm256_storeu_si256(lanes.as_mut_ptr() as *mut __m256i, *word) };
+        for (state, lane) in states.iter_mut().zip(lanes.iter()) {
+            state[j] = *lane;
+        }
+    }
+    // 키 상태를 담았던 임시 벡터와 레인 버퍼를 지웁니다.
+    w.zeroize();
+    lanes.zeroize();
 }
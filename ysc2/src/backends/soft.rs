@@ -1,33 +1,57 @@
-use crate::{stream::Ysc2StreamCore};
-use crate::variant::Ysc2Variant;
+use crate::stream::{StreamRevision, Ysc2KeystreamCore};
+use crate::variant::{Revision, Ysc2Variant};
 use crate::consts::*;
-use cipher::{Block, BlockSizeUser, ParBlocksSizeUser, StreamBackend};
+use cipher::{Block, BlockSizeUser, ParBlocks, ParBlocksSizeUser, StreamBackend};
+use zeroize::Zeroize;
 
 /// The software (scalar) backend for YSC2.
-pub struct Backend<'a, V: Ysc2Variant>(pub(crate) &'a mut Ysc2StreamCore<V>);
+pub struct Backend<'a, V: Ysc2Variant, R: StreamRevision>(pub(crate) &'a mut Ysc2KeystreamCore<V, R>);
 
-impl<'a, V: Ysc2Variant> BlockSizeUser for Backend<'a, V> {
+impl<'a, V: Ysc2Variant, R: StreamRevision> BlockSizeUser for Backend<'a, V, R> {
     type BlockSize = cipher::consts::U128;
 }
 
-// Re-add ParBlocksSizeUser for compatibility with cipher v0.4.4
-impl<'a, V: Ysc2Variant> ParBlocksSizeUser for Backend<'a, V> {
-    type ParBlocksSize = cipher::consts::U128;
+/// 병렬로 생성하는 키스트림 블록 수
+pub(crate) const PAR_BLOCKS: usize = 4;
+
+impl<'a, V: Ysc2Variant, R: StreamRevision> ParBlocksSizeUser for Backend<'a, V, R> {
+    type ParBlocksSize = cipher::consts::U4;
 }
 
-impl<'a, V: Ysc2Variant> StreamBackend for Backend<'a, V> {
+impl<'a, V: Ysc2Variant, R: StreamRevision> StreamBackend for Backend<'a, V, R> {
     #[inline]
     fn gen_ks_block(&mut self, output: &mut Block<Self>) {
-        self.0.counter = self.0.counter.wrapping_add(1);
+        let counter = self.0.next_block_counter();
 
-        let mut working_state = self.0.state;
-        working_state[0] ^= self.0.counter;
+        let mut states = [self.0.state];
+        super::add_counter::<R>(&mut states[0], counter);
+        permutation::<V>(&mut states[0]);
+        super::squeeze_blocks::<R, 1>(&mut states, |states| permutation::<V>(&mut states[0]));
 
-        permutation::<V>(&mut working_state);
+        for (chunk, word) in output.chunks_exact_mut(8).zip(states[0].iter()) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        states.zeroize();
+    }
 
-        for (i, chunk) in output.chunks_exact_mut(8).enumerate() {
-            chunk.copy_from_slice(&working_state[i].to_le_bytes());
+    /// 카운터 블록 `PAR_BLOCKS`개를 교차(interleave) 계산합니다.
+    #[inline]
+    fn gen_par_ks_blocks(&mut self, blocks: &mut ParBlocks<Self>) {
+        let mut states = [self.0.state; PAR_BLOCKS];
+        for state in states.iter_mut() {
+            let counter = self.0.next_block_counter();
+            super::add_counter::<R>(state, counter);
         }
+
+        permutation_par::<V, PAR_BLOCKS>(&mut states);
+        super::squeeze_blocks::<R, PAR_BLOCKS>(&mut states, permutation_par::<V, PAR_BLOCKS>);
+
+        for (block, state) in blocks.iter_mut().zip(states.iter()) {
+            for (chunk, word) in block.chunks_exact_mut(8).zip(state.iter()) {
+                chunk.copy_from_slice(&word.to_le_bytes());
+            }
+        }
+        states.zeroize();
     }
 }
 
@@ -39,12 +63,26 @@ fn g(x: u64) -> u64 {
     x ^ (x.rotate_left(ROT_A) & x.rotate_left(ROT_B))
 }
 
+/// 라운드 상수 더하기 (AddRoundConstant). 상수와 위치는 변형의 리비전이 정합니다.
+#[inline(always)]
+fn add_round_constant<V: Ysc2Variant>(state: &mut [u64; 16], round: usize, lfsr: &mut LfsrRoundConstants) {
+    match V::REVISION {
+        Revision::V1 => state[0] ^= round_constant(round),
+        Revision::V2 => {
+            for (word, rc) in state.iter_mut().zip(lfsr.constants(round)) {
+                *word ^= rc;
+            }
+        }
+    }
+}
+
 /// The state permutation function based on the (2x2) Lai-Massey structure.
 #[inline(always)]
 pub(crate) fn permutation<V: Ysc2Variant>(state: &mut [u64; 16]) {
-    for r in 0..V::ROUNDS {
+    let mut lfsr = LfsrRoundConstants::new();
+    for round in 0..V::ROUNDS {
         // 1. 라운드 상수 더하기 (AddRoundConstant)
-        state[0] ^= RC[r];
+        add_round_constant::<V>(state, round, &mut lfsr);
 
         // 2. 비선형 계층 (Non-linear Layer) - Lai-Massey 유사 구조
         let mut temp = [0u64; 8];
@@ -65,4 +103,54 @@ pub(crate) fn permutation<V: Ysc2Variant>(state: &mut [u64; 16]) {
         }
         *state = new_state;
     }
-}
\ No newline at end of file
+}
+
+/// The inverse of [`permutation`]: undoes the rounds in reverse order.
+#[inline(always)]
+pub(crate) fn permutation_inverse<V: Ysc2Variant>(state: &mut [u64; 16]) {
+    let mut lfsr = LfsrRoundConstants::new();
+    for round in (0..V::ROUNDS).rev() {
+        // 3. 선형 계층의 역: state[P[i]] = new_state[i]
+        let mut old_state = [0u64; 16];
+        for i in 0..16 {
+            old_state[P[i]] = state[i];
+        }
+        *state = old_state;
+
+        // 2. 비선형 계층의 역
+        for i in 0..8 {
+            state[i] ^= state[i + 8]; // L = L' ^ R'
+        }
+        for i in 0..8 {
+            state[i + 8] ^= g(state[i]); // R = R' ^ g(L)
+        }
+
+        // 1. 라운드 상수 제거
+        add_round_constant::<V>(state, round, &mut lfsr);
+    }
+}
+
+/// Applies the permutation to `N` independent states, interleaving their
+/// operations so the CPU can overlap them.
+#[inline(always)]
+pub(crate) fn permutation_par<V: Ysc2Variant, const N: usize>(states: &mut [[u64; 16]; N]) {
+    let mut lfsr = LfsrRoundConstants::new();
+    for round in 0..V::ROUNDS {
+        for state in states.iter_mut() {
+            add_round_constant::<V>(state, round, &mut lfsr);
+        }
+        for i in 0..8 {
+            for state in states.iter_mut() {
+                state[i + 8] ^= g(state[i]); // R' = R ^ g(L)
+            }
+        }
+        for i in 0..8 {
+            for state in states.iter_mut() {
+                state[i] ^= state[i + 8]; // L' = L ^ R'
+            }
+        }
+        for state in states.iter_mut() {
+            *state = core::array::from_fn(|i| state[P[i]]);
+        }
+    }
+}
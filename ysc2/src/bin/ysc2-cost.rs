@@ -0,0 +1,99 @@
+//======================================================================
+// src/bin/ysc2-cost.rs
+// 라운드 수별 FHE 비용(AND 깊이, 게이트 수)을 표로 출력합니다.
+//
+// 사용법: ysc2-cost [--rounds 4,8,12] [--message-bytes 64] [--revision 1|2]
+//======================================================================
+
+use std::process::ExitCode;
+use ysc2::cost::{self, Cost};
+use ysc2::variant::{Ysc2_1024, Ysc2_1024V2, Ysc2_512, Ysc2_512V2, Ysc2Variant};
+
+struct Args {
+    rounds: Vec<usize>,
+    message_bytes: usize,
+    revision: u8,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args { rounds: vec![4, 6, 8, 10, 12], message_bytes: 64, revision: 1 };
+    let mut iter = std::env::args().skip(1);
+    while let Some(flag) = iter.next() {
+        let value = iter.next().ok_or_else(|| format!("missing value for {flag}"))?;
+        match flag.as_str() {
+            "--rounds" => {
+                args.rounds = value
+                    .split(',')
+                    .map(|r| r.trim().parse().map_err(|_| format!("invalid round count: {r}")))
+                    .collect::<Result<_, _>>()?;
+            }
+            "--message-bytes" => {
+                args.message_bytes = value.parse().map_err(|_| format!("invalid byte count: {value}"))?;
+            }
+            "--revision" => {
+                args.revision = match value.as_str() {
+                    "1" => 1,
+                    "2" => 2,
+                    _ => return Err(format!("unknown revision: {value}")),
+                };
+            }
+            _ => return Err(format!("unknown argument: {flag}")),
+        }
+    }
+    Ok(args)
+}
+
+fn print_row(name: &str, rounds: usize, cost: &Cost) {
+    println!(
+        "{name:<24} {rounds:>6} {:>9} {:>12} {:>12} {:>10} {:>10}",
+        cost.and_depth, cost.and_gates, cost.xor_gates, cost.not_gates, cost.rotations
+    );
+}
+
+fn print_variant<V: Ysc2Variant>(label: &str, args: &Args) {
+    for &rounds in &args.rounds {
+        let report = cost::report::<V>(rounds, args.message_bytes);
+        print_row(&format!("{label} permutation"), rounds, &report.permutation);
+        print_row(&format!("{label} keystream"), rounds, &report.keystream_block);
+        print_row(&format!("{label} hash"), rounds, &report.hash);
+        print_row(&format!("{label} aead"), rounds, &report.aead);
+    }
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("ysc2-cost: {err}");
+            eprintln!("usage: ysc2-cost [--rounds 4,8,12] [--message-bytes 64] [--revision 1|2]");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    println!("revision: {}, message: {} bytes (hash and AEAD)", args.revision, args.message_bytes);
+    println!(
+        "{:<24} {:>6} {:>9} {:>12} {:>12} {:>10} {:>10}",
+        "construction", "rounds", "AND depth", "AND", "XOR", "NOT", "rotations"
+    );
+    if args.revision == 1 {
+        print_variant::<Ysc2_512>("YSC2-512", &args);
+        print_variant::<Ysc2_1024>("YSC2-1024", &args);
+    } else {
+        print_variant::<Ysc2_512V2>("YSC2-512", &args);
+        print_variant::<Ysc2_1024V2>("YSC2-1024", &args);
+    }
+
+    // AuxCrypt의 상수표 길이를 넘는 라운드 수는 건너뜁니다.
+    #[cfg(feature = "auxcrypt")]
+    {
+        use auxcrypt::variant::Revision;
+
+        let revision = if args.revision == 1 { Revision::V1 } else { Revision::V2 };
+        for &rounds in args.rounds.iter().filter(|&&r| r <= auxcrypt::consts::RC.len()) {
+            print_row("AuxCrypt permutation", rounds, &cost::auxcrypt_permutation_cost(revision, rounds));
+            print_row("AuxCrypt V3 permutation", rounds, &cost::auxcrypt_permutation_cost(Revision::V3, rounds));
+        }
+    }
+
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,203 @@
+//======================================================================
+// src/cascade.rs
+// YSC2와 AuxCrypt 키스트림을 XOR하는 캐스케이드 암호 (`auxcrypt` 기능)
+//
+// 하나의 마스터 키에서 YSC2 하위 키는 YSC2 순열로, AuxCrypt 하위 키는
+// AuxCrypt 순열로 각각 유도하므로, 어느 한 순열이 깨져도 다른 쪽 키스트림은
+// 안전하게 남습니다. 아핀 순열(AuxCrypt 리비전 1, 2)은 그 자체로 깨져 있으므로
+// AuxCrypt 쪽은 `NonAffineVariant`만 받습니다.
+//======================================================================
+
+use crate::consts::RATE_BYTES;
+use crate::stream::{init_state, squeeze_key, Ysc2SpongeStreamCore, MODE_CASCADE_KEY};
+use crate::variant::Ysc2Variant;
+use auxcrypt::stream::AuxCryptStream;
+use auxcrypt::variant::{AuxCryptVariant, NonAffineVariant};
+use auxcrypt::{AuxCryptState, STATE_BYTES};
+use cipher::{
+    generic_array::GenericArray, inout::InOutBuf, Iv, IvSizeUser, Key, KeyIvInit, KeySizeUser, OverflowError,
+    SeekNum, StreamCipher, StreamCipherCoreWrapper, StreamCipherError, StreamCipherSeek,
+};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Domain separator placed in the capacity of the AuxCrypt key derivation.
+const AUXCRYPT_KEY_DOMAIN: &[u8] = b"YSC2-CASCADE-AUXCRYPT-KEY";
+
+/// Derives the YSC2 subkey from the master key with the YSC2 permutation only.
+fn ysc2_subkey<V: Ysc2Variant>(master: &[u8]) -> GenericArray<u8, V::KeySize> {
+    let mut subkey = GenericArray::default();
+    squeeze_key::<V>(init_state::<V>(MODE_CASCADE_KEY, master, &[]), &mut subkey);
+    subkey
+}
+
+/// Derives the AuxCrypt subkey from the master key with the AuxCrypt permutation only.
+///
+/// A sponge over the AuxCrypt permutation: the domain separator and the
+/// master key length sit in the capacity, the key is absorbed in 64-byte
+/// blocks and the subkey is squeezed from the rate.
+fn auxcrypt_subkey<A: AuxCryptVariant>(master: &[u8]) -> GenericArray<u8, A::KeySize> {
+    let mut state = AuxCryptState::new();
+    state.xor_in(RATE_BYTES, AUXCRYPT_KEY_DOMAIN);
+    state.xor_in(STATE_BYTES - 8, &(master.len() as u64).to_le_bytes());
+    for block in master.chunks(RATE_BYTES) {
+        state.xor_in(0, block);
+        state.permute::<A>();
+    }
+
+    let mut subkey = GenericArray::<u8, A::KeySize>::default();
+    for (i, block) in subkey.chunks_mut(RATE_BYTES).enumerate() {
+        if i > 0 {
+            state.permute::<A>();
+        }
+        state.extract(0, block);
+    }
+    subkey
+}
+
+/// Returns the YSC2 and AuxCrypt subkeys the cascade ciphers derive from `master`.
+///
+/// Each subkey comes from its own permutation, so breaking one permutation
+/// reveals nothing about the other subkey.
+pub fn derive_subkeys<V: Ysc2Variant, A: AuxCryptVariant>(
+    master: &GenericArray<u8, V::KeySize>,
+) -> (GenericArray<u8, V::KeySize>, GenericArray<u8, A::KeySize>) {
+    (ysc2_subkey::<V>(master), auxcrypt_subkey::<A>(master))
+}
+
+/// YSC2 (sponge stream) and AuxCrypt keystreams XORed together.
+///
+/// Both ciphers run under independent subkeys derived from one master key of
+/// `V::KEY_SIZE` bytes, each with its own permutation, and share the nonce.
+/// The result stays secure as long as either permutation is, which is why
+/// `A` must be a [`NonAffineVariant`]: an affine AuxCrypt keystream is a
+/// linear function of its subkey and adds nothing once YSC2 falls.
+pub struct CascadeStreamCipher<V: Ysc2Variant, A: NonAffineVariant<NonceSize = V::NonceSize>> {
+    ysc2: StreamCipherCoreWrapper<Ysc2SpongeStreamCore<V>>,
+    auxcrypt: AuxCryptStream<A>,
+}
+
+impl<V: Ysc2Variant, A: NonAffineVariant<NonceSize = V::NonceSize>> KeySizeUser for CascadeStreamCipher<V, A> {
+    type KeySize = V::KeySize;
+}
+
+impl<V: Ysc2Variant, A: NonAffineVariant<NonceSize = V::NonceSize>> IvSizeUser for CascadeStreamCipher<V, A> {
+    type IvSize = V::NonceSize;
+}
+
+impl<V: Ysc2Variant, A: NonAffineVariant<NonceSize = V::NonceSize>> KeyIvInit for CascadeStreamCipher<V, A> {
+    fn new(key: &Key<Self>, iv: &Iv<Self>) -> Self {
+        let mut ysc2_key = ysc2_subkey::<V>(key);
+        let mut auxcrypt_key = auxcrypt_subkey::<A>(key);
+        let cipher = Self {
+            ysc2: KeyIvInit::new(&ysc2_key, iv),
+            auxcrypt: KeyIvInit::new(&auxcrypt_key, iv),
+        };
+        ysc2_key.as_mut_slice().zeroize();
+        auxcrypt_key.as_mut_slice().zeroize();
+        cipher
+    }
+}
+
+impl<V: Ysc2Variant, A: NonAffineVariant<NonceSize = V::NonceSize>> StreamCipher for CascadeStreamCipher<V, A> {
+    fn try_apply_keystream_inout(&mut self, mut buf: InOutBuf<'_, '_, u8>) -> Result<(), StreamCipherError> {
+        // 두 스트림은 위치와 키스트림 길이가 같으므로 함께 성공하거나 함께 실패합니다.
+        self.ysc2.try_apply_keystream_inout(buf.reborrow())?;
+        self.auxcrypt.try_apply_keystream(buf.into_out())
+    }
+}
+
+impl<V: Ysc2Variant, A: NonAffineVariant<NonceSize = V::NonceSize>> StreamCipherSeek for CascadeStreamCipher<V, A> {
+    fn try_current_pos<T: SeekNum>(&self) -> Result<T, OverflowError> {
+        self.ysc2.try_current_pos()
+    }
+
+    fn try_seek<T: SeekNum>(&mut self, pos: T) -> Result<(), StreamCipherError> {
+        self.ysc2.try_seek(pos)?;
+        let pos: u128 = self.ysc2.try_current_pos()?;
+        self.auxcrypt.try_seek(pos)
+    }
+}
+
+impl<V: Ysc2Variant, A: NonAffineVariant<NonceSize = V::NonceSize>> ZeroizeOnDrop for CascadeStreamCipher<V, A> {}
+
+#[cfg(feature = "ysc2x")]
+pub use self::aead_impl::CascadeAead;
+
+#[cfg(feature = "ysc2x")]
+mod aead_impl {
+    use super::{auxcrypt_subkey, ysc2_subkey};
+    use crate::aead::Ysc2Aead;
+    use crate::variant::Ysc2Variant;
+    use aead::{consts::{U0, U16}, generic_array::GenericArray, AeadCore, AeadInPlace, Key, KeyInit, KeySizeUser, Nonce, Tag};
+    use auxcrypt::stream::AuxCryptStream;
+    use auxcrypt::variant::NonAffineVariant;
+    use cipher::{KeyIvInit, StreamCipher};
+    use zeroize::{Zeroize, ZeroizeOnDrop};
+
+    /// Cascade AEAD: the plaintext is first encrypted with the AuxCrypt stream,
+    /// then sealed with [`Ysc2Aead`], each under its own subkey of the master key.
+    ///
+    /// Confidentiality holds as long as either permutation is secure (`A` is
+    /// a [`NonAffineVariant`] for that reason); authenticity rests on the
+    /// YSC2 duplex.
+    #[derive(Clone)]
+    pub struct CascadeAead<V: Ysc2Variant, A: NonAffineVariant<NonceSize = V::NonceSize>> {
+        ysc2: Ysc2Aead<V>,
+        auxcrypt_key: GenericArray<u8, A::KeySize>,
+    }
+
+    impl<V: Ysc2Variant, A: NonAffineVariant<NonceSize = V::NonceSize>> Drop for CascadeAead<V, A> {
+        fn drop(&mut self) {
+            self.auxcrypt_key.as_mut_slice().zeroize();
+        }
+    }
+
+    impl<V: Ysc2Variant, A: NonAffineVariant<NonceSize = V::NonceSize>> ZeroizeOnDrop for CascadeAead<V, A> {}
+
+    impl<V: Ysc2Variant, A: NonAffineVariant<NonceSize = V::NonceSize>> KeySizeUser for CascadeAead<V, A> {
+        type KeySize = V::KeySize;
+    }
+
+    impl<V: Ysc2Variant, A: NonAffineVariant<NonceSize = V::NonceSize>> KeyInit for CascadeAead<V, A> {
+        fn new(key: &Key<Self>) -> Self {
+            let mut ysc2_key = ysc2_subkey::<V>(key);
+            let aead = Self { ysc2: Ysc2Aead::new(&ysc2_key), auxcrypt_key: auxcrypt_subkey::<A>(key) };
+            ysc2_key.as_mut_slice().zeroize();
+            aead
+        }
+    }
+
+    impl<V: Ysc2Variant, A: NonAffineVariant<NonceSize = V::NonceSize>> AeadCore for CascadeAead<V, A> {
+        type NonceSize = V::NonceSize;
+        type TagSize = U16;
+        type CiphertextOverhead = U0;
+    }
+
+    impl<V: Ysc2Variant, A: NonAffineVariant<NonceSize = V::NonceSize>> AeadInPlace for CascadeAead<V, A> {
+        fn encrypt_in_place_detached(
+            &self,
+            nonce: &Nonce<Self>,
+            associated_data: &[u8],
+            buffer: &mut [u8],
+        ) -> aead::Result<Tag<Self>> {
+            AuxCryptStream::<A>::new(&self.auxcrypt_key, nonce)
+                .try_apply_keystream(buffer)
+                .map_err(|_| aead::Error)?;
+            self.ysc2.encrypt_in_place_detached(nonce, associated_data, buffer)
+        }
+
+        fn decrypt_in_place_detached(
+            &self,
+            nonce: &Nonce<Self>,
+            associated_data: &[u8],
+            buffer: &mut [u8],
+            tag: &Tag<Self>,
+        ) -> aead::Result<()> {
+            // 인증에 실패하면 Ysc2Aead가 버퍼를 지우므로 AuxCrypt 층은 건너뜁니다.
+            self.ysc2.decrypt_in_place_detached(nonce, associated_data, buffer, tag)?;
+            AuxCryptStream::<A>::new(&self.auxcrypt_key, nonce)
+                .try_apply_keystream(buffer)
+                .map_err(|_| aead::Error)
+        }
+    }
+}
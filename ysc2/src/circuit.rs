@@ -0,0 +1,366 @@
+//======================================================================
+// src/circuit.rs
+// YSC2 순열과 스트림 키스트림 함수를 게이트 수준 넷리스트로 내보냅니다.
+// (Bristol Fashion 및 간단한 JSON 게이트 목록)
+//======================================================================
+
+use crate::consts::STATE_WORDS;
+use crate::generic::{self, BitOps, Bitwise};
+use crate::variant::Ysc2Variant;
+use core::fmt::Write;
+
+/// Index of a wire in a [`Circuit`].
+pub type Wire = usize;
+
+/// A single gate. The last field is always the output wire.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Gate {
+    /// `out = a ^ b`
+    Xor(Wire, Wire, Wire),
+    /// `out = a & b`
+    And(Wire, Wire, Wire),
+    /// `out = !a`
+    Inv(Wire, Wire),
+    /// `out = a` (wire copy)
+    Eqw(Wire, Wire),
+    /// `out = constant`
+    Eq(bool, Wire),
+}
+
+/// A Boolean circuit in Bristol Fashion layout: input wires come first and
+/// output wires are the last wires of the circuit.
+///
+/// The YSC2 circuits only compute with `AND`, `XOR` and `INV` gates. Rotations
+/// and the word permutation `P` are pure rewiring and cost nothing; round
+/// constants become `INV` gates on the bits that are set.
+///
+/// Every value is a sequence of 64-bit words, and bit `k` (least significant
+/// first) of word `i` is wire `64 * i + k` of that value. Words map to bytes
+/// in little-endian order, as everywhere else in the crate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Circuit {
+    input_sizes: Vec<usize>,
+    output_sizes: Vec<usize>,
+    num_wires: usize,
+    gates: Vec<Gate>,
+}
+
+impl Circuit {
+    /// Bit sizes of the input values, in order.
+    pub fn input_sizes(&self) -> &[usize] {
+        &self.input_sizes
+    }
+
+    /// Bit sizes of the output values, in order.
+    pub fn output_sizes(&self) -> &[usize] {
+        &self.output_sizes
+    }
+
+    /// Total number of wires.
+    pub fn num_wires(&self) -> usize {
+        self.num_wires
+    }
+
+    /// The gates in evaluation order.
+    pub fn gates(&self) -> &[Gate] {
+        &self.gates
+    }
+
+    /// Number of `AND` gates, the only gates that are not free in FHE.
+    pub fn and_count(&self) -> usize {
+        self.gates.iter().filter(|g| matches!(g, Gate::And(..))).count()
+    }
+
+    /// Number of `XOR` gates.
+    pub fn xor_count(&self) -> usize {
+        self.gates.iter().filter(|g| matches!(g, Gate::Xor(..))).count()
+    }
+
+    /// Number of `INV` gates.
+    pub fn inv_count(&self) -> usize {
+        self.gates.iter().filter(|g| matches!(g, Gate::Inv(..))).count()
+    }
+
+    /// Evaluates the circuit on plaintext bits.
+    ///
+    /// `inputs` holds all input values concatenated; the result holds all
+    /// output values concatenated.
+    ///
+    /// # Panics
+    /// Panics if `inputs` does not have exactly as many bits as the circuit inputs.
+    pub fn evaluate(&self, inputs: &[bool]) -> Vec<bool> {
+        let num_inputs: usize = self.input_sizes.iter().sum();
+        let num_outputs: usize = self.output_sizes.iter().sum();
+        assert_eq!(inputs.len(), num_inputs, "wrong number of input bits");
+
+        let mut wires = vec![false; self.num_wires];
+        wires[..num_inputs].copy_from_slice(inputs);
+        for gate in &self.gates {
+            match *gate {
+                Gate::Xor(a, b, out) => wires[out] = wires[a] ^ wires[b],
+                Gate::And(a, b, out) => wires[out] = wires[a] & wires[b],
+                Gate::Inv(a, out) => wires[out] = !wires[a],
+                Gate::Eqw(a, out) => wires[out] = wires[a],
+                Gate::Eq(value, out) => wires[out] = value,
+            }
+        }
+        wires.split_off(self.num_wires - num_outputs)
+    }
+
+    /// Serializes the circuit in Bristol Fashion.
+    pub fn to_bristol(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "{} {}", self.gates.len(), self.num_wires).unwrap();
+        write!(out, "{}", self.input_sizes.len()).unwrap();
+        for size in &self.input_sizes {
+            write!(out, " {size}").unwrap();
+        }
+        write!(out, "\n{}", self.output_sizes.len()).unwrap();
+        for size in &self.output_sizes {
+            write!(out, " {size}").unwrap();
+        }
+        out.push_str("\n\n");
+        for gate in &self.gates {
+            match *gate {
+                Gate::Xor(a, b, o) => writeln!(out, "2 1 {a} {b} {o} XOR"),
+                Gate::And(a, b, o) => writeln!(out, "2 1 {a} {b} {o} AND"),
+                Gate::Inv(a, o) => writeln!(out, "1 1 {a} {o} INV"),
+                Gate::Eqw(a, o) => writeln!(out, "1 1 {a} {o} EQW"),
+                Gate::Eq(v, o) => writeln!(out, "1 1 {} {o} EQ", v as u8),
+            }
+            .unwrap();
+        }
+        out
+    }
+
+    /// Serializes the circuit as a JSON gate list:
+    ///
+    /// `{"inputs":[..],"outputs":[..],"wires":n,"gates":[{"op":"AND","in":[a,b],"out":c},..]}`
+    ///
+    /// `EQ` gates carry their constant in `"value"` instead of `"in"`.
+    pub fn to_json(&self) -> String {
+        let list = |sizes: &[usize]| {
+            sizes.iter().map(|s| s.to_string()).collect::<Vec<_>>().join(",")
+        };
+        let mut out = String::new();
+        write!(
+            out,
+            "{{\"inputs\":[{}],\"outputs\":[{}],\"wires\":{},\"gates\":[",
+            list(&self.input_sizes),
+            list(&self.output_sizes),
+            self.num_wires
+        )
+        .unwrap();
+        for (i, gate) in self.gates.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            match *gate {
+                Gate::Xor(a, b, o) => write!(out, "{{\"op\":\"XOR\",\"in\":[{a},{b}],\"out\":{o}}}"),
+                Gate::And(a, b, o) => write!(out, "{{\"op\":\"AND\",\"in\":[{a},{b}],\"out\":{o}}}"),
+                Gate::Inv(a, o) => write!(out, "{{\"op\":\"INV\",\"in\":[{a}],\"out\":{o}}}"),
+                Gate::Eqw(a, o) => write!(out, "{{\"op\":\"EQW\",\"in\":[{a}],\"out\":{o}}}"),
+                Gate::Eq(v, o) => write!(out, "{{\"op\":\"EQ\",\"value\":{},\"out\":{o}}}", v as u8),
+            }
+            .unwrap();
+        }
+        out.push_str("]}");
+        out
+    }
+}
+
+/// The netlist of `Ysc2Permutation<V>`: one 1024-bit input, one 1024-bit output.
+pub fn permutation_circuit<V: Ysc2Variant>() -> Circuit {
+    let mut ops = Bitwise(Builder::new(&[STATE_WORDS * 64]));
+    let words = ops.0.input_words(0, STATE_WORDS);
+    let mut state: [Word; STATE_WORDS] = words.try_into().unwrap();
+    generic::permutation::<V, _>(&mut ops, &mut state);
+    ops.0.finish(&[&state])
+}
+
+/// The netlist of one `Ysc2SpongeStreamCore<V>` keystream block.
+///
+/// Inputs are the key (`V::KEY_SIZE` bytes), the nonce (`V::NONCE_SIZE` bytes)
+/// and the 64-bit block counter; the output is the 1024-bit keystream block.
+/// The counter is the value used for the block, i.e. the first block uses `1`.
+pub fn keystream_circuit<V: Ysc2Variant>() -> Circuit {
+    stream_circuit::<V>(generic::stream_init_sponge::<V, Bitwise<Builder>>, generic::keystream_block_sponge::<V, Bitwise<Builder>>)
+}
+
+/// The netlist of one `Ysc2LegacyStreamCore<V>` keystream block, with the same
+/// inputs and output as [`keystream_circuit`].
+pub fn legacy_keystream_circuit<V: Ysc2Variant>() -> Circuit {
+    stream_circuit::<V>(generic::stream_init::<V, Bitwise<Builder>>, generic::keystream_block::<V, Bitwise<Builder>>)
+}
+
+type InitFn = fn(&mut Bitwise<Builder>, &[Word], &[Word]) -> [Word; STATE_WORDS];
+type BlockFn = fn(&mut Bitwise<Builder>, &[Word; STATE_WORDS], &Word) -> [Word; STATE_WORDS];
+
+fn stream_circuit<V: Ysc2Variant>(stream_init: InitFn, keystream_block: BlockFn) -> Circuit {
+    let key_words = V::KEY_SIZE / 8;
+    let nonce_words = V::NONCE_SIZE / 8;
+    let mut ops = Bitwise(Builder::new(&[V::KEY_SIZE * 8, V::NONCE_SIZE * 8, 64]));
+    let key = ops.0.input_words(0, key_words);
+    let nonce = ops.0.input_words(key_words * 64, nonce_words);
+    let counter = ops.0.input_words((key_words + nonce_words) * 64, 1);
+
+    let state = stream_init(&mut ops, &key, &nonce);
+    let block = keystream_block(&mut ops, &state, &counter[0]);
+    ops.0.finish(&[&block])
+}
+
+//======================================================================
+// Builder - 상수 접기(constant folding)를 하며 게이트를 쌓습니다.
+//======================================================================
+
+/// A bit during construction: either a known constant or a wire.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Bit {
+    Const(bool),
+    Wire(Wire),
+}
+
+type Word = [Bit; 64];
+
+struct Builder {
+    input_sizes: Vec<usize>,
+    num_inputs: usize,
+    next_wire: Wire,
+    gates: Vec<Gate>,
+}
+
+impl BitOps for Builder {
+    type Bit = Bit;
+
+    fn constant(&mut self, value: bool) -> Bit {
+        Bit::Const(value)
+    }
+
+    fn xor(&mut self, a: &Bit, b: &Bit) -> Bit {
+        match (*a, *b) {
+            (Bit::Const(x), Bit::Const(y)) => Bit::Const(x ^ y),
+            (Bit::Const(false), w) | (w, Bit::Const(false)) => w,
+            (Bit::Const(true), w) | (w, Bit::Const(true)) => self.not(&w),
+            (Bit::Wire(x), Bit::Wire(y)) => self.gate(|out| Gate::Xor(x, y, out)),
+        }
+    }
+
+    fn and(&mut self, a: &Bit, b: &Bit) -> Bit {
+        match (*a, *b) {
+            (Bit::Const(x), Bit::Const(y)) => Bit::Const(x & y),
+            (Bit::Const(false), _) | (_, Bit::Const(false)) => Bit::Const(false),
+            (Bit::Const(true), w) | (w, Bit::Const(true)) => w,
+            (Bit::Wire(x), Bit::Wire(y)) => self.gate(|out| Gate::And(x, y, out)),
+        }
+    }
+
+    fn not(&mut self, a: &Bit) -> Bit {
+        match *a {
+            Bit::Const(x) => Bit::Const(!x),
+            Bit::Wire(x) => self.gate(|out| Gate::Inv(x, out)),
+        }
+    }
+}
+
+impl Builder {
+    fn new(input_sizes: &[usize]) -> Self {
+        let num_inputs = input_sizes.iter().sum();
+        Self {
+            input_sizes: input_sizes.to_vec(),
+            num_inputs,
+            next_wire: num_inputs,
+            gates: Vec::new(),
+        }
+    }
+
+    fn input_words(&self, first_wire: Wire, count: usize) -> Vec<Word> {
+        (0..count)
+            .map(|i| core::array::from_fn(|k| Bit::Wire(first_wire + 64 * i + k)))
+            .collect()
+    }
+
+    fn fresh(&mut self) -> Wire {
+        let wire = self.next_wire;
+        self.next_wire += 1;
+        wire
+    }
+
+    /// 새 출력 와이어를 가진 게이트를 추가합니다.
+    fn gate(&mut self, make: impl FnOnce(Wire) -> Gate) -> Bit {
+        let out = self.fresh();
+        self.gates.push(make(out));
+        Bit::Wire(out)
+    }
+
+    /// 출력 비트가 회로의 마지막 와이어가 되도록 번호를 다시 매깁니다.
+    fn finish(mut self, outputs: &[&[Word]]) -> Circuit {
+        let output_sizes = outputs.iter().map(|words| words.len() * 64).collect();
+        let out_bits: Vec<Bit> = outputs.iter().flat_map(|words| words.iter().flatten()).copied().collect();
+
+        // 게이트 출력이면서 아직 다른 출력 위치에 쓰이지 않은 와이어는 그대로 옮기고,
+        // 입력 와이어·상수·중복 와이어는 복사 게이트로 새 와이어를 만듭니다.
+        let mut claimed = vec![false; self.next_wire];
+        let mut out_wires = Vec::with_capacity(out_bits.len());
+        for bit in out_bits {
+            let wire = match bit {
+                Bit::Wire(w) if w >= self.num_inputs && !claimed[w] => w,
+                Bit::Wire(w) => {
+                    let out = self.fresh();
+                    self.gates.push(Gate::Eqw(w, out));
+                    out
+                }
+                Bit::Const(v) => {
+                    let out = self.fresh();
+                    self.gates.push(Gate::Eq(v, out));
+                    out
+                }
+            };
+            if wire >= claimed.len() {
+                claimed.resize(wire + 1, false);
+            }
+            claimed[wire] = true;
+            out_wires.push(wire);
+        }
+
+        let num_wires = self.next_wire;
+        let first_output = num_wires - out_wires.len();
+        let mut map: Vec<Wire> = (0..num_wires).collect();
+        let mut next_internal = self.num_inputs;
+        for gate in &self.gates {
+            let out = gate_output(gate);
+            if !claimed[out] {
+                map[out] = next_internal;
+                next_internal += 1;
+            }
+        }
+        for (i, &wire) in out_wires.iter().enumerate() {
+            map[wire] = first_output + i;
+        }
+
+        let gates = self
+            .gates
+            .iter()
+            .map(|gate| match *gate {
+                Gate::Xor(a, b, o) => Gate::Xor(map[a], map[b], map[o]),
+                Gate::And(a, b, o) => Gate::And(map[a], map[b], map[o]),
+                Gate::Inv(a, o) => Gate::Inv(map[a], map[o]),
+                Gate::Eqw(a, o) => Gate::Eqw(map[a], map[o]),
+                Gate::Eq(v, o) => Gate::Eq(v, map[o]),
+            })
+            .collect();
+
+        Circuit {
+            input_sizes: self.input_sizes,
+            output_sizes,
+            num_wires,
+            gates,
+        }
+    }
+}
+
+fn gate_output(gate: &Gate) -> Wire {
+    match *gate {
+        Gate::Xor(_, _, o) | Gate::And(_, _, o) => o,
+        Gate::Inv(_, o) | Gate::Eqw(_, o) | Gate::Eq(_, o) => o,
+    }
+}
@@ -9,11 +9,185 @@ pub const RATE_BYTES: usize = 64;
 /// The internal state size in bytes.
 pub const STATE_BYTES: usize = STATE_WORDS * 8;
 
+/// Shortest key accepted by the variable-length constructors
+/// ([`Ysc2SpongeStreamCore::new_variable`](crate::stream::Ysc2KeystreamCore::new_variable)
+/// and `Ysc2Aead::new_variable`).
+pub const MIN_KEY_BYTES: usize = 16;
+
 /// 라운드 상수 (RC) - 간단한 IOTA 값 사용
+///
+/// 처음 16개 라운드의 상수표입니다. 라운드 수에 제한이 없도록 순열은
+/// [`round_constant`]를 사용합니다.
 pub const RC: [u64; 16] = [
     0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,
 ];
 
+/// Round constant of round `round` (0-based), for any number of rounds.
+///
+/// Agrees with [`RC`] on its first 16 entries.
+#[inline(always)]
+pub const fn round_constant(round: usize) -> u64 {
+    round as u64
+}
+
+/// Round constants of a `rounds`-round permutation, in order.
+#[inline(always)]
+pub fn round_constants(rounds: usize) -> impl DoubleEndedIterator<Item = u64> + ExactSizeIterator {
+    (0..rounds).map(round_constant)
+}
+
+/// LFSR 시드: π의 소수부 첫 64비트 (nothing-up-my-sleeve 값)
+pub const LFSR_SEED: u64 = 0x243F_6A88_85A3_08D3;
+
+/// One step of the xorshift64 (13, 7, 17) generator, a maximal-length LFSR
+/// over GF(2) with period 2^64 - 1.
+#[inline(always)]
+pub const fn lfsr_next(x: u64) -> u64 {
+    let x = x ^ (x << 13);
+    let x = x ^ (x >> 7);
+    x ^ (x << 17)
+}
+
+/// Computes the [`Revision::V2`](crate::variant::Revision::V2) round constants
+/// of round `round` from scratch.
+///
+/// Word `i` of round `r` is the LFSR output after `16 * r + i + 1` steps from
+/// [`LFSR_SEED`], so every round XORs a distinct full-width constant into each
+/// state word.
+const fn lfsr_words(round: usize) -> [u64; STATE_WORDS] {
+    let mut x = LFSR_SEED;
+    let mut step = 0;
+    while step < STATE_WORDS * round {
+        x = lfsr_next(x);
+        step += 1;
+    }
+    let mut words = [0u64; STATE_WORDS];
+    let mut i = 0;
+    while i < STATE_WORDS {
+        x = lfsr_next(x);
+        words[i] = x;
+        i += 1;
+    }
+    words
+}
+
+/// Number of rounds whose LFSR constants are precomputed in [`LFSR_RC`].
+pub const LFSR_RC_ROUNDS: usize = 32;
+
+/// 리비전 2 라운드 상수표 (처음 [`LFSR_RC_ROUNDS`]개 라운드)
+pub const LFSR_RC: [[u64; STATE_WORDS]; LFSR_RC_ROUNDS] = {
+    let mut table = [[0u64; STATE_WORDS]; LFSR_RC_ROUNDS];
+    let mut round = 0;
+    while round < LFSR_RC_ROUNDS {
+        table[round] = lfsr_words(round);
+        round += 1;
+    }
+    table
+};
+
+/// [`Revision::V2`](crate::variant::Revision::V2) round constants of round
+/// `round` (0-based), one per state word, for any number of rounds.
+///
+/// Past [`LFSR_RC`] this steps the LFSR from the end of the table, so the
+/// permutation walks its rounds with [`LfsrRoundConstants`] instead.
+#[inline(always)]
+pub fn lfsr_round_constants(round: usize) -> [u64; STATE_WORDS] {
+    LfsrRoundConstants::new().constants(round)
+}
+
+/// Inverse of [`lfsr_next`].
+#[inline(always)]
+pub const fn lfsr_prev(x: u64) -> u64 {
+    let x = undo_xorshift_left(x, 17);
+    let x = undo_xorshift_right(x, 7);
+    undo_xorshift_left(x, 13)
+}
+
+/// `y = x ^ (x << k)`의 역: `x = y ^ (y << k) ^ (y << 2k) ^ ...`
+const fn undo_xorshift_left(y: u64, k: u32) -> u64 {
+    let mut x = y;
+    let mut shift = k;
+    while shift < 64 {
+        x ^= y << shift;
+        shift += k;
+    }
+    x
+}
+
+/// `y = x ^ (x >> k)`의 역
+const fn undo_xorshift_right(y: u64, k: u32) -> u64 {
+    let mut x = y;
+    let mut shift = k;
+    while shift < 64 {
+        x ^= y >> shift;
+        shift += k;
+    }
+    x
+}
+
+/// Cursor over the [`Revision::V2`](crate::variant::Revision::V2) round
+/// constants that carries the LFSR state from one round to the next.
+///
+/// Asking for the round after or before the last one costs one LFSR step per
+/// word, so a permutation of any length walks its constants in linear time,
+/// forwards or in reverse.
+#[derive(Clone)]
+pub struct LfsrRoundConstants {
+    round: usize,
+    words: [u64; STATE_WORDS],
+}
+
+impl LfsrRoundConstants {
+    /// Creates a cursor positioned on round 0.
+    pub const fn new() -> Self {
+        Self { round: 0, words: LFSR_RC[0] }
+    }
+
+    /// Returns the constants of round `round` and moves the cursor there.
+    pub fn constants(&mut self, round: usize) -> [u64; STATE_WORDS] {
+        if round < LFSR_RC_ROUNDS {
+            self.words = LFSR_RC[round];
+            self.round = round;
+        } else if round + 1 == self.round {
+            self.step_back();
+        } else {
+            if round < self.round || self.round < LFSR_RC_ROUNDS - 1 {
+                // 상수표의 마지막 라운드에서 다시 출발합니다.
+                self.words = LFSR_RC[LFSR_RC_ROUNDS - 1];
+                self.round = LFSR_RC_ROUNDS - 1;
+            }
+            while self.round < round {
+                self.step_forward();
+            }
+        }
+        self.words
+    }
+
+    fn step_forward(&mut self) {
+        let mut x = self.words[STATE_WORDS - 1];
+        for word in self.words.iter_mut() {
+            x = lfsr_next(x);
+            *word = x;
+        }
+        self.round += 1;
+    }
+
+    fn step_back(&mut self) {
+        let mut x = self.words[0];
+        for word in self.words.iter_mut().rev() {
+            x = lfsr_prev(x);
+            *word = x;
+        }
+        self.round -= 1;
+    }
+}
+
+impl Default for LfsrRoundConstants {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// 비선형 함수 g(x)에 사용될 회전 상수
 pub const ROT_A: u32 = 13;
 pub const ROT_B: u32 = 37;
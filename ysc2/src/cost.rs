@@ -0,0 +1,342 @@
+//======================================================================
+// src/cost.rs
+// FHE 비용 계획을 위한 AND 깊이 및 게이트 수 분석기
+//======================================================================
+
+use crate::consts::{RATE_BYTES, STATE_WORDS};
+use crate::generic::{self, WordOps};
+use crate::variant::{Revision, Ysc2Variant};
+use core::ops::Add;
+
+/// Homomorphic cost of a computation on encrypted bits.
+///
+/// Gate counts are bit-level (a 64-bit word AND is 64 `AND` gates); rotations
+/// are counted per word and are free on bit-encrypted data. Operations with
+/// public constants are free, except that XOR with a constant counts one `NOT`
+/// per set bit. Inputs that are not secret (nonce, associated data, message
+/// for hashing) are treated as encrypted unless stated otherwise, which gives
+/// an upper bound.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Cost {
+    /// Multiplicative (AND) depth of the deepest output bit.
+    pub and_depth: u32,
+    /// Number of `AND` gates.
+    pub and_gates: u64,
+    /// Number of `XOR` gates.
+    pub xor_gates: u64,
+    /// Number of `NOT` gates.
+    pub not_gates: u64,
+    /// Number of 64-bit word rotations.
+    pub rotations: u64,
+}
+
+/// Sequential composition: depths and gate counts add up.
+impl Add for Cost {
+    type Output = Cost;
+
+    fn add(self, rhs: Cost) -> Cost {
+        Cost {
+            and_depth: self.and_depth + rhs.and_depth,
+            and_gates: self.and_gates + rhs.and_gates,
+            xor_gates: self.xor_gates + rhs.xor_gates,
+            not_gates: self.not_gates + rhs.not_gates,
+            rotations: self.rotations + rhs.rotations,
+        }
+    }
+}
+
+/// A [`WordOps`] implementation that evaluates nothing and counts gates.
+///
+/// A word is represented by the AND depth of its deepest bit, or `None` for a
+/// public constant.
+#[derive(Clone, Debug, Default)]
+pub struct CostCounter {
+    cost: Cost,
+}
+
+impl CostCounter {
+    /// Creates a counter with all counts at zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a fresh encrypted input word (depth 0).
+    pub fn input(&self) -> Option<u32> {
+        Some(0)
+    }
+
+    /// Returns the gates counted so far. `and_depth` is left at zero; take it
+    /// from the output words.
+    pub fn gates(&self) -> Cost {
+        self.cost
+    }
+
+    /// Returns the counted gates together with the depth of `outputs`.
+    pub fn finish(&self, outputs: &[Option<u32>]) -> Cost {
+        Cost {
+            and_depth: outputs.iter().flatten().copied().max().unwrap_or(0),
+            ..self.cost
+        }
+    }
+}
+
+impl WordOps for CostCounter {
+    type Word = Option<u32>;
+
+    fn constant(&mut self, _value: u64) -> Option<u32> {
+        None
+    }
+
+    fn xor(&mut self, a: &Option<u32>, b: &Option<u32>) -> Option<u32> {
+        match (*a, *b) {
+            (Some(x), Some(y)) => {
+                self.cost.xor_gates += 64;
+                Some(x.max(y))
+            }
+            (Some(x), None) | (None, Some(x)) => Some(x),
+            (None, None) => None,
+        }
+    }
+
+    fn and(&mut self, a: &Option<u32>, b: &Option<u32>) -> Option<u32> {
+        match (*a, *b) {
+            (Some(x), Some(y)) => {
+                self.cost.and_gates += 64;
+                Some(x.max(y) + 1)
+            }
+            (Some(x), None) | (None, Some(x)) => Some(x),
+            (None, None) => None,
+        }
+    }
+
+    fn not(&mut self, a: &Option<u32>) -> Option<u32> {
+        if a.is_some() {
+            self.cost.not_gates += 64;
+        }
+        *a
+    }
+
+    fn rotate_left(&mut self, a: &Option<u32>, _n: u32) -> Option<u32> {
+        if a.is_some() {
+            self.cost.rotations += 1;
+        }
+        *a
+    }
+
+    fn xor_constant(&mut self, a: &Option<u32>, value: u64) -> Option<u32> {
+        if a.is_some() {
+            self.cost.not_gates += value.count_ones() as u64;
+        }
+        *a
+    }
+}
+
+fn encrypted_state(ops: &CostCounter) -> [Option<u32>; STATE_WORDS] {
+    [ops.input(); STATE_WORDS]
+}
+
+// 키만 암호화되어 있고 Nonce, 카운터 등 나머지는 공개값입니다.
+fn keyed_state<V: Ysc2Variant>(ops: &CostCounter) -> [Option<u32>; STATE_WORDS] {
+    core::array::from_fn(|i| if i < V::KEY_SIZE / 8 { ops.input() } else { None })
+}
+
+/// Cost of the YSC2 permutation of the given revision with `rounds` rounds on
+/// an encrypted state.
+pub fn permutation_cost(revision: Revision, rounds: usize) -> Cost {
+    let mut ops = CostCounter::new();
+    let mut state = encrypted_state(&ops);
+    generic::permutation_rounds(&mut ops, &mut state, revision, rounds);
+    ops.finish(&state)
+}
+
+/// Cost of keying `Ysc2SpongeStreamCore<V>` with an encrypted key and public
+/// nonce, then producing `blocks` keystream blocks (the blocks are independent,
+/// so the depth is that of one block after initialisation). Each block takes
+/// two permutations, one per half; the legacy core needs only one.
+pub fn keystream_cost<V: Ysc2Variant>(rounds: usize, blocks: usize) -> Cost {
+    let mut ops = CostCounter::new();
+    // 초기화: 키 블록마다 순열 한 번, Nonce(공개값)에 한 번
+    let mut state = [None; STATE_WORDS];
+    for _ in 0..V::KEY_SIZE.div_ceil(RATE_BYTES) {
+        for word in state.iter_mut().take(RATE_BYTES / 8) {
+            *word = ops.xor(word, &ops.input());
+        }
+        generic::permutation_rounds(&mut ops, &mut state, V::REVISION, rounds);
+    }
+    generic::permutation_rounds(&mut ops, &mut state, V::REVISION, rounds);
+
+    let mut depth = 0;
+    for _ in 0..blocks {
+        let mut x = state;
+        for word in x.iter_mut().take(RATE_BYTES / 8) {
+            *word = ops.xor_constant(word, 1);
+        }
+        generic::permutation_rounds(&mut ops, &mut x, V::REVISION, rounds);
+        // 두 번째 절반 rate(P(P(x)))가 가장 깊습니다.
+        generic::permutation_rounds(&mut ops, &mut x, V::REVISION, rounds);
+        depth = depth.max(ops.finish(&x[..RATE_BYTES / 8]).and_depth);
+    }
+    Cost { and_depth: depth, ..ops.gates() }
+}
+
+/// Cost of hashing an encrypted `message_bytes`-byte message with YSC2-X and
+/// squeezing `output_bytes` bytes.
+pub fn hash_cost(revision: Revision, rounds: usize, message_bytes: usize, output_bytes: usize) -> Cost {
+    let mut ops = CostCounter::new();
+    let mut state = [None; STATE_WORDS];
+
+    // 흡수: 메시지와 패딩을 합쳐 64바이트 블록마다 순열 한 번
+    for _ in 0..message_bytes / RATE_BYTES + 1 {
+        for word in state.iter_mut().take(RATE_BYTES / 8) {
+            *word = ops.xor(word, &ops.input());
+        }
+        generic::permutation_rounds(&mut ops, &mut state, revision, rounds);
+    }
+    // 짜내기: 출력 64바이트마다 순열 한 번
+    for _ in 0..output_bytes.div_ceil(RATE_BYTES) {
+        generic::permutation_rounds(&mut ops, &mut state, revision, rounds);
+    }
+    ops.finish(&state[..RATE_BYTES / 8])
+}
+
+/// Cost of one `Ysc2Aead<V>` encryption with an encrypted key, public nonce
+/// and associated data, and an encrypted `message_bytes`-byte plaintext,
+/// including the tag.
+pub fn aead_cost<V: Ysc2Variant>(rounds: usize, message_bytes: usize) -> Cost {
+    let mut ops = CostCounter::new();
+    let mut state = keyed_state::<V>(&ops);
+
+    // 도메인, Nonce, AD 흡수: 길이와 무관하게 각각 순열 한 번
+    for _ in 0..3 {
+        generic::permutation_rounds(&mut ops, &mut state, V::REVISION, rounds);
+    }
+    // 평문 청크마다: 키스트림 짜내기 한 번 + 암호문 흡수 한 번
+    let mut ciphertext_depth = 0;
+    for _ in 0..message_bytes.div_ceil(RATE_BYTES) {
+        generic::permutation_rounds(&mut ops, &mut state, V::REVISION, rounds);
+        for word in state.iter_mut().take(RATE_BYTES / 8) {
+            let ciphertext = ops.xor(word, &ops.input());
+            ciphertext_depth = ciphertext_depth.max(ciphertext.unwrap_or(0));
+            *word = ops.xor(word, &ciphertext);
+        }
+        generic::permutation_rounds(&mut ops, &mut state, V::REVISION, rounds);
+    }
+    // 태그
+    generic::permutation_rounds(&mut ops, &mut state, V::REVISION, rounds);
+    let tag_depth = ops.finish(&state[..2]).and_depth;
+    Cost { and_depth: tag_depth.max(ciphertext_depth), ..ops.gates() }
+}
+
+/// Costs of every YSC2 construction for one parameter set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Report {
+    /// Number of permutation rounds.
+    pub rounds: usize,
+    /// One permutation call.
+    pub permutation: Cost,
+    /// Stream cipher: keying plus one keystream block.
+    pub keystream_block: Cost,
+    /// YSC2-X hash of the message with a 64-byte digest.
+    pub hash: Cost,
+    /// YSC2-X AEAD encryption of the message.
+    pub aead: Cost,
+}
+
+/// Reports the costs of every construction of variant `V` (with its revision)
+/// with `rounds` rounds, for a `message_bytes`-byte message.
+pub fn report<V: Ysc2Variant>(rounds: usize, message_bytes: usize) -> Report {
+    Report {
+        rounds,
+        permutation: permutation_cost(V::REVISION, rounds),
+        keystream_block: keystream_cost::<V>(rounds, 1),
+        hash: hash_cost(V::REVISION, rounds, message_bytes, RATE_BYTES),
+        aead: aead_cost::<V>(rounds, message_bytes),
+    }
+}
+
+//======================================================================
+// AuxCrypt 비교
+//======================================================================
+
+/// The AuxCrypt permutation of the given revision with `rounds` rounds,
+/// evaluated with `ops`.
+///
+/// This mirrors `auxcrypt::backends::soft::permutation`; a test checks that
+/// both stay identical.
+///
+/// # Panics
+/// Panics if `rounds` exceeds the AuxCrypt round constant tables
+/// (`auxcrypt::consts::RC.len()` rounds).
+#[cfg(feature = "auxcrypt")]
+pub fn auxcrypt_permutation_rounds<O: WordOps>(
+    ops: &mut O,
+    state: &mut [O::Word; STATE_WORDS],
+    revision: auxcrypt::variant::Revision,
+    rounds: usize,
+) {
+    use auxcrypt::consts::{LFSR_RC, P, RC, ROT_A, ROT_B, ROT_C, ROT_D};
+    use auxcrypt::variant::Revision;
+
+    assert!(rounds <= RC.len(), "AuxCrypt has round constants for at most {} rounds", RC.len());
+    let v3 = revision == Revision::V3;
+    // f(x) = (¬x) ⊕ (x <<< R_A) ⊕ (x <<< R_B) (V3: ⊕ (x <<< R_C) ∧ (x <<< R_D));
+    // 한 쌍에 대한 Lai-Massey 단계 (V3: 첫 워드에 σ 적용)
+    let step = |ops: &mut O, state: &mut [O::Word; STATE_WORDS], a: usize, b: usize| {
+        let x = ops.xor(&state[a], &state[b]);
+        let not_x = ops.not(&x);
+        let rot_a = ops.rotate_left(&x, ROT_A);
+        let rot_b = ops.rotate_left(&x, ROT_B);
+        let t = ops.xor(&not_x, &rot_a);
+        let mut diff = ops.xor(&t, &rot_b);
+        if v3 {
+            let rot_c = ops.rotate_left(&x, ROT_C);
+            let rot_d = ops.rotate_left(&x, ROT_D);
+            let quadratic = ops.and(&rot_c, &rot_d);
+            diff = ops.xor(&diff, &quadratic);
+        }
+        state[a] = ops.xor(&state[a], &diff);
+        state[b] = ops.xor(&state[b], &diff);
+        if v3 {
+            // σ(hi ‖ lo) = lo ‖ (hi ⊕ lo)
+            let swapped = ops.rotate_left(&state[a], 32);
+            let low_mask = ops.constant(0xFFFF_FFFF);
+            let low = ops.and(&state[a], &low_mask);
+            state[a] = ops.xor(&swapped, &low);
+        }
+    };
+
+    for round in 0..rounds {
+        match revision {
+            Revision::V1 => state[0] = ops.xor_constant(&state[0], RC[round]),
+            Revision::V2 | Revision::V3 => {
+                for (word, rc) in state.iter_mut().zip(LFSR_RC[round]) {
+                    *word = ops.xor_constant(word, rc);
+                }
+            }
+        }
+        for i in 0..8 { step(ops, state, 2 * i, 2 * i + 1); }
+        for i in 0..4 {
+            step(ops, state, 4 * i, 4 * i + 2);
+            step(ops, state, 4 * i + 1, 4 * i + 3);
+        }
+        for i in 0..2 {
+            for j in 0..4 { step(ops, state, 8 * i + j, 8 * i + j + 4); }
+        }
+        for i in 0..8 { step(ops, state, i, i + 8); }
+        *state = core::array::from_fn(|i| state[P[i]].clone());
+    }
+}
+
+/// Cost of the AuxCrypt permutation of the given revision with `rounds` rounds
+/// on an encrypted state.
+///
+/// # Panics
+/// Panics if `rounds` exceeds the AuxCrypt round constant tables, as
+/// [`auxcrypt_permutation_rounds`] does.
+#[cfg(feature = "auxcrypt")]
+pub fn auxcrypt_permutation_cost(revision: auxcrypt::variant::Revision, rounds: usize) -> Cost {
+    let mut ops = CostCounter::new();
+    let mut state = encrypted_state(&ops);
+    auxcrypt_permutation_rounds(&mut ops, &mut state, revision, rounds);
+    ops.finish(&state)
+}
@@ -0,0 +1,191 @@
+//======================================================================
+// src/dual.rs
+// YSC2와 AuxCrypt 두 순열의 스펀지 출력을 결합하는 해시 / KDF (`auxcrypt` 기능)
+//======================================================================
+
+//! Dual-permutation sponge: the same input is absorbed by a YSC2 sponge and
+//! an AuxCrypt sponge, and the output is the XOR of both squeezed streams.
+//!
+//! # Construction
+//!
+//! Both sponges use a 1024-bit state, a 64-byte rate and a 64-byte capacity.
+//!
+//! 1. Each state starts at zero with the domain separator `D` XORed into the
+//!    capacity (bytes 64..). The separator is followed by one byte naming the
+//!    sponge, `0x01` for YSC2 and `0x02` for AuxCrypt.
+//! 2. The message `M` is padded to `M || 0x80 || 0*`, a multiple of 64 bytes.
+//!    A message whose length is already a multiple of 64 still gets a full
+//!    padding block.
+//! 3. Each padded block is XORed into the rate, followed by the sponge's
+//!    permutation: `P` of variant `V` for YSC2, `Q` of variant `A` for AuxCrypt.
+//! 4. Output block `i` is `rate(P^(i+1)(y)) ^ rate(Q^(i+1)(a))`, where `y` and
+//!    `a` are the states after absorption.
+//!
+//! The hasher uses `D = "YSC2-DUAL-HASH"`. [`dual_kdf`] uses
+//! `D = "YSC2-DUAL-KDF"` and absorbs `le64(|salt|) || salt || le64(|ikm|) ||
+//! ikm || le64(|info|) || info`.
+//!
+//! # Security
+//!
+//! The output is pseudorandom as long as either sponge is, which is what key
+//! derivation needs. An affine AuxCrypt permutation gives no such sponge, so
+//! `A` must be a [`NonAffineVariant`].
+//!
+//! XOR is not a robust combiner for collision resistance, so use a
+//! single-permutation hash where only that matters.
+
+use crate::backends;
+use crate::consts::{RATE_BYTES, STATE_WORDS};
+use crate::variant::Ysc2Variant;
+use auxcrypt::variant::NonAffineVariant;
+use auxcrypt::AuxCryptPermutation;
+use core::marker::PhantomData;
+use digest::{
+    block_buffer::Eager,
+    core_api::{Block, BlockSizeUser, Buffer, BufferKindUser, CoreWrapper, ExtendableOutputCore, UpdateCore, XofReaderCore, XofReaderCoreWrapper},
+    ExtendableOutput, HashMarker, Update, XofReader,
+};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Domain separator of [`DualPermutationHasher`].
+pub const HASH_DOMAIN: &str = "YSC2-DUAL-HASH";
+
+/// Domain separator of [`dual_kdf`].
+pub const KDF_DOMAIN: &str = "YSC2-DUAL-KDF";
+
+/// 스펀지 구분 바이트 (도메인 바로 뒤)
+const YSC2_SPONGE: u8 = 0x01;
+const AUXCRYPT_SPONGE: u8 = 0x02;
+
+/// 용량 영역에 도메인과 스펀지 구분 바이트를 실은 초기 상태입니다.
+fn initial_state(domain: &str, sponge: u8) -> [u64; STATE_WORDS] {
+    let domain = domain.as_bytes();
+    assert!(domain.len() < RATE_BYTES, "domain separator does not fit in the capacity");
+    let mut capacity = [0u8; RATE_BYTES];
+    capacity[..domain.len()].copy_from_slice(domain);
+    capacity[domain.len()] = sponge;
+
+    let mut state = [0u64; STATE_WORDS];
+    for (word, chunk) in state[8..].iter_mut().zip(capacity.chunks_exact(8)) {
+        *word = u64::from_le_bytes(chunk.try_into().unwrap());
+    }
+    state
+}
+
+fn xor_block(state: &mut [u64; STATE_WORDS], block: &[u8]) {
+    for (word, chunk) in state.iter_mut().zip(block.chunks_exact(8)) {
+        *word ^= u64::from_le_bytes(chunk.try_into().unwrap());
+    }
+}
+
+/// The core of [`DualPermutationHasher`]: a YSC2 sponge and an AuxCrypt sponge
+/// absorbing the same blocks.
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+pub struct DualCore<V: Ysc2Variant, A: NonAffineVariant> {
+    ysc2: [u64; STATE_WORDS],
+    auxcrypt: [u64; STATE_WORDS],
+    _variant: PhantomData<(V, A)>,
+}
+
+impl<V: Ysc2Variant, A: NonAffineVariant> DualCore<V, A> {
+    /// Creates a core for the domain separator `domain` (shorter than 64 bytes).
+    ///
+    /// # Panics
+    /// Panics if `domain` is 64 bytes or longer.
+    pub fn with_domain(domain: &str) -> Self {
+        Self {
+            ysc2: initial_state(domain, YSC2_SPONGE),
+            auxcrypt: initial_state(domain, AUXCRYPT_SPONGE),
+            _variant: PhantomData,
+        }
+    }
+
+    fn absorb_block(&mut self, block: &[u8]) {
+        xor_block(&mut self.ysc2, block);
+        xor_block(&mut self.auxcrypt, block);
+        backends::permutation::<V>(&mut self.ysc2);
+        AuxCryptPermutation::<A>::permute(&mut self.auxcrypt);
+    }
+}
+
+impl<V: Ysc2Variant, A: NonAffineVariant> Default for DualCore<V, A> {
+    fn default() -> Self {
+        Self::with_domain(HASH_DOMAIN)
+    }
+}
+
+impl<V: Ysc2Variant, A: NonAffineVariant> HashMarker for DualCore<V, A> {}
+
+impl<V: Ysc2Variant, A: NonAffineVariant> BlockSizeUser for DualCore<V, A> {
+    type BlockSize = digest::consts::U64;
+}
+
+impl<V: Ysc2Variant, A: NonAffineVariant> BufferKindUser for DualCore<V, A> {
+    type BufferKind = Eager;
+}
+
+impl<V: Ysc2Variant, A: NonAffineVariant> UpdateCore for DualCore<V, A> {
+    #[inline]
+    fn update_blocks(&mut self, blocks: &[Block<Self>]) {
+        for block in blocks {
+            self.absorb_block(block);
+        }
+    }
+}
+
+impl<V: Ysc2Variant, A: NonAffineVariant> ExtendableOutputCore for DualCore<V, A> {
+    type ReaderCore = DualReader<V, A>;
+
+    fn finalize_xof_core(&mut self, buffer: &mut Buffer<Self>) -> Self::ReaderCore {
+        let final_block = buffer.get_data();
+        let mut padded_block = Block::<Self>::default();
+        padded_block[..final_block.len()].copy_from_slice(final_block);
+        padded_block[final_block.len()] = 0x80;
+        self.absorb_block(&padded_block);
+        padded_block.as_mut_slice().zeroize();
+
+        DualReader { ysc2: self.ysc2, auxcrypt: self.auxcrypt, _variant: PhantomData }
+    }
+}
+
+/// Squeezes the XOR of both sponges' output blocks.
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+pub struct DualReader<V: Ysc2Variant, A: NonAffineVariant> {
+    ysc2: [u64; STATE_WORDS],
+    auxcrypt: [u64; STATE_WORDS],
+    _variant: PhantomData<(V, A)>,
+}
+
+impl<V: Ysc2Variant, A: NonAffineVariant> BlockSizeUser for DualReader<V, A> {
+    type BlockSize = digest::consts::U64;
+}
+
+impl<V: Ysc2Variant, A: NonAffineVariant> XofReaderCore for DualReader<V, A> {
+    fn read_block(&mut self) -> Block<Self> {
+        backends::permutation::<V>(&mut self.ysc2);
+        AuxCryptPermutation::<A>::permute(&mut self.auxcrypt);
+
+        let mut block = Block::<Self>::default();
+        for (i, chunk) in block.chunks_exact_mut(8).enumerate() {
+            chunk.copy_from_slice(&(self.ysc2[i] ^ self.auxcrypt[i]).to_le_bytes());
+        }
+        block
+    }
+}
+
+/// Extendable-output hash over both permutations (see the [module docs](self)).
+pub type DualPermutationHasher<V, A> = CoreWrapper<DualCore<V, A>>;
+
+/// Reader returned by [`DualPermutationHasher`].
+pub type DualPermutationReader<V, A> = XofReaderCoreWrapper<DualReader<V, A>>;
+
+/// Derives `out.len()` bytes of key material from `ikm`, `salt` and `info`
+/// over both permutations (see the [module docs](self)).
+pub fn dual_kdf<V: Ysc2Variant, A: NonAffineVariant>(ikm: &[u8], salt: &[u8], info: &[u8], out: &mut [u8]) {
+    let mut hasher = DualPermutationHasher::<V, A>::from_core(DualCore::with_domain(KDF_DOMAIN));
+    for field in [salt, ikm, info] {
+        hasher.update(&(field.len() as u64).to_le_bytes());
+        hasher.update(field);
+    }
+    hasher.finalize_xof().read(out);
+}
@@ -0,0 +1,252 @@
+//======================================================================
+// src/generic.rs
+// 사용자 정의 비트/워드 연산 위에서 YSC2 순열과 키스트림을 평가합니다.
+// (TFHE 등 동형 암호문 타입으로 트랜스사이퍼링을 하기 위한 경로)
+//======================================================================
+
+use crate::consts::{round_constant, LfsrRoundConstants, P, RATE_BYTES, ROT_A, ROT_B, STATE_WORDS};
+use crate::stream::{stream_iv, INIT_PHASE_KEY, INIT_PHASE_NONCE};
+use crate::variant::{Revision, Ysc2Variant};
+
+/// rate의 워드 수
+const RATE_WORDS: usize = RATE_BYTES / 8;
+
+/// Operations on an abstract 64-bit word, e.g. 64 encrypted bits.
+///
+/// The permutation only needs XOR, AND, NOT and rotations, so any type that
+/// provides them (a plain `u64`, a vector of FHE ciphertexts, a circuit
+/// builder, ...) can evaluate YSC2. Operations take `&mut self` so that an
+/// implementation can carry an evaluation key or gather statistics.
+pub trait WordOps {
+    /// The word type.
+    type Word: Clone;
+
+    /// Returns the word for a public constant.
+    fn constant(&mut self, value: u64) -> Self::Word;
+    /// Bitwise XOR.
+    fn xor(&mut self, a: &Self::Word, b: &Self::Word) -> Self::Word;
+    /// Bitwise AND.
+    fn and(&mut self, a: &Self::Word, b: &Self::Word) -> Self::Word;
+    /// Bitwise NOT.
+    fn not(&mut self, a: &Self::Word) -> Self::Word;
+    /// Left rotation by `n` bits (`0 < n < 64`).
+    fn rotate_left(&mut self, a: &Self::Word, n: u32) -> Self::Word;
+
+    /// XOR with a public constant.
+    ///
+    /// The default goes through [`WordOps::constant`]; implementations can
+    /// override it to avoid materializing the constant.
+    fn xor_constant(&mut self, a: &Self::Word, value: u64) -> Self::Word {
+        let c = self.constant(value);
+        self.xor(a, &c)
+    }
+}
+
+/// Operations on an abstract bit, e.g. a single encrypted bit.
+///
+/// Wrap an implementation in [`Bitwise`] to get a [`WordOps`] whose words are
+/// `[B::Bit; 64]` and whose rotations are free.
+pub trait BitOps {
+    /// The bit type.
+    type Bit: Clone;
+
+    /// Returns the bit for a public constant.
+    fn constant(&mut self, value: bool) -> Self::Bit;
+    /// XOR of two bits.
+    fn xor(&mut self, a: &Self::Bit, b: &Self::Bit) -> Self::Bit;
+    /// AND of two bits.
+    fn and(&mut self, a: &Self::Bit, b: &Self::Bit) -> Self::Bit;
+    /// NOT of a bit.
+    fn not(&mut self, a: &Self::Bit) -> Self::Bit;
+
+    /// XOR with a public constant: the identity for `false`, NOT for `true`.
+    fn xor_constant(&mut self, a: &Self::Bit, value: bool) -> Self::Bit {
+        if value { self.not(a) } else { a.clone() }
+    }
+}
+
+/// Lifts a [`BitOps`] implementation to 64-bit words (bit `k` is `word[k]`, LSB first).
+#[derive(Clone, Debug, Default)]
+pub struct Bitwise<B>(pub B);
+
+impl<B: BitOps> WordOps for Bitwise<B> {
+    type Word = [B::Bit; 64];
+
+    fn constant(&mut self, value: u64) -> Self::Word {
+        core::array::from_fn(|k| self.0.constant((value >> k) & 1 == 1))
+    }
+
+    fn xor(&mut self, a: &Self::Word, b: &Self::Word) -> Self::Word {
+        core::array::from_fn(|k| self.0.xor(&a[k], &b[k]))
+    }
+
+    fn and(&mut self, a: &Self::Word, b: &Self::Word) -> Self::Word {
+        core::array::from_fn(|k| self.0.and(&a[k], &b[k]))
+    }
+
+    fn not(&mut self, a: &Self::Word) -> Self::Word {
+        core::array::from_fn(|k| self.0.not(&a[k]))
+    }
+
+    fn rotate_left(&mut self, a: &Self::Word, n: u32) -> Self::Word {
+        core::array::from_fn(|k| a[(k + 64 - n as usize) % 64].clone())
+    }
+
+    fn xor_constant(&mut self, a: &Self::Word, value: u64) -> Self::Word {
+        core::array::from_fn(|k| self.0.xor_constant(&a[k], (value >> k) & 1 == 1))
+    }
+}
+
+/// Plaintext words; evaluates exactly like the `soft` backend.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PlainWords;
+
+impl WordOps for PlainWords {
+    type Word = u64;
+
+    fn constant(&mut self, value: u64) -> u64 { value }
+    fn xor(&mut self, a: &u64, b: &u64) -> u64 { a ^ b }
+    fn and(&mut self, a: &u64, b: &u64) -> u64 { a & b }
+    fn not(&mut self, a: &u64) -> u64 { !a }
+    fn rotate_left(&mut self, a: &u64, n: u32) -> u64 { a.rotate_left(n) }
+}
+
+/// Plaintext bits; useful to test a [`BitOps`]-based pipeline without encryption.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PlainBits;
+
+impl BitOps for PlainBits {
+    type Bit = bool;
+
+    fn constant(&mut self, value: bool) -> bool { value }
+    fn xor(&mut self, a: &bool, b: &bool) -> bool { a ^ b }
+    fn and(&mut self, a: &bool, b: &bool) -> bool { a & b }
+    fn not(&mut self, a: &bool) -> bool { !a }
+}
+
+/// 비선형 함수 g(x) = x ^ ((x <<< A) & (x <<< B))
+fn g<O: WordOps>(ops: &mut O, x: &O::Word) -> O::Word {
+    let rot_a = ops.rotate_left(x, ROT_A);
+    let rot_b = ops.rotate_left(x, ROT_B);
+    let t = ops.and(&rot_a, &rot_b);
+    ops.xor(x, &t)
+}
+
+/// The YSC2 permutation of variant `V`, evaluated with `ops`.
+pub fn permutation<V: Ysc2Variant, O: WordOps>(ops: &mut O, state: &mut [O::Word; STATE_WORDS]) {
+    permutation_rounds(ops, state, V::REVISION, V::ROUNDS);
+}
+
+/// The YSC2 permutation of the given revision, reduced (or extended) to
+/// `rounds` rounds.
+pub fn permutation_rounds<O: WordOps>(
+    ops: &mut O,
+    state: &mut [O::Word; STATE_WORDS],
+    revision: Revision,
+    rounds: usize,
+) {
+    let mut lfsr = LfsrRoundConstants::new();
+    for round in 0..rounds {
+        match revision {
+            Revision::V1 => state[0] = ops.xor_constant(&state[0], round_constant(round)),
+            Revision::V2 => {
+                for (word, rc) in state.iter_mut().zip(lfsr.constants(round)) {
+                    *word = ops.xor_constant(word, rc);
+                }
+            }
+        }
+        for i in 0..8 {
+            let t = g(ops, &state[i]);
+            state[i + 8] = ops.xor(&state[i + 8], &t); // R' = R ^ g(L)
+        }
+        for i in 0..8 {
+            state[i] = ops.xor(&state[i], &state[i + 8]); // L' = L ^ R'
+        }
+        *state = core::array::from_fn(|i| state[P[i]].clone());
+    }
+}
+
+/// The keyed state of `Ysc2LegacyStreamCore<V>` for the given key and nonce words
+/// (`V::KEY_SIZE / 8` and `V::NONCE_SIZE / 8` little-endian words).
+///
+/// # Panics
+/// Panics if `key` or `nonce` has the wrong number of words.
+pub fn stream_init<V: Ysc2Variant, O: WordOps>(
+    ops: &mut O,
+    key: &[O::Word],
+    nonce: &[O::Word],
+) -> [O::Word; STATE_WORDS] {
+    assert_eq!(key.len(), V::KEY_SIZE / 8, "wrong key length");
+    assert_eq!(nonce.len(), V::NONCE_SIZE / 8, "wrong nonce length");
+
+    // Ysc2KeystreamCore::new: 키를 앞에서부터 싣고 Nonce를 후반부에 XOR합니다.
+    let zero = ops.constant(0);
+    let mut state: [O::Word; STATE_WORDS] = core::array::from_fn(|_| zero.clone());
+    state[..key.len()].clone_from_slice(key);
+    for (i, word) in nonce.iter().enumerate() {
+        state[i + 8] = ops.xor(&state[i + 8], word);
+    }
+    permutation::<V, O>(ops, &mut state);
+    state
+}
+
+/// The keyed state of `Ysc2SpongeStreamCore<V>`: the domain-separated
+/// initialisation phase absorbing the key and nonce words (see
+/// [`stream_iv`]).
+///
+/// # Panics
+/// Panics if `key` or `nonce` has the wrong number of words.
+pub fn stream_init_sponge<V: Ysc2Variant, O: WordOps>(
+    ops: &mut O,
+    key: &[O::Word],
+    nonce: &[O::Word],
+) -> [O::Word; STATE_WORDS] {
+    assert_eq!(key.len(), V::KEY_SIZE / 8, "wrong key length");
+    assert_eq!(nonce.len(), V::NONCE_SIZE / 8, "wrong nonce length");
+
+    let iv = stream_iv::<V>();
+    let mut state: [O::Word; STATE_WORDS] =
+        core::array::from_fn(|i| ops.constant(if i < 8 { 0 } else { iv[i - 8] }));
+    let blocks = key.chunks(8).map(|words| (words, INIT_PHASE_KEY));
+    for (words, phase) in blocks.chain(nonce.chunks(8).map(|words| (words, INIT_PHASE_NONCE))) {
+        for (i, word) in words.iter().enumerate() {
+            state[i] = ops.xor(&state[i], word);
+        }
+        state[15] = ops.xor_constant(&state[15], phase);
+        permutation::<V, O>(ops, &mut state);
+    }
+    state
+}
+
+/// One keystream block of `Ysc2LegacyStreamCore<V>`: `P(state ^ counter)`.
+///
+/// `counter` is the value used for the block, i.e. the first block uses `1`.
+pub fn keystream_block<V: Ysc2Variant, O: WordOps>(
+    ops: &mut O,
+    state: &[O::Word; STATE_WORDS],
+    counter: &O::Word,
+) -> [O::Word; STATE_WORDS] {
+    let mut block = state.clone();
+    block[0] = ops.xor(&block[0], counter);
+    permutation::<V, O>(ops, &mut block);
+    block
+}
+
+/// One keystream block of `Ysc2SpongeStreamCore<V>`: `rate(P(x)) || rate(P(P(x)))`
+/// with `x` the state with `counter` XORed into each rate word.
+///
+/// `counter` is the value used for the block, i.e. the first block uses `1`.
+pub fn keystream_block_sponge<V: Ysc2Variant, O: WordOps>(
+    ops: &mut O,
+    state: &[O::Word; STATE_WORDS],
+    counter: &O::Word,
+) -> [O::Word; STATE_WORDS] {
+    let mut x = state.clone();
+    for word in x.iter_mut().take(RATE_WORDS) {
+        *word = ops.xor(word, counter);
+    }
+    permutation::<V, O>(ops, &mut x);
+    let first = x.clone();
+    permutation::<V, O>(ops, &mut x);
+    core::array::from_fn(|i| if i < RATE_WORDS { first[i].clone() } else { x[i - RATE_WORDS].clone() })
+}
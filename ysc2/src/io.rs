@@ -0,0 +1,282 @@
+//======================================================================
+// src/io.rs
+// 스트림 암호를 std::io의 Read / Write / Seek에 연결하는 어댑터
+//======================================================================
+
+//! `std::io` adapters for any seekable stream cipher, e.g.
+//! [`Ysc2_512SpongeStreamCipher`](crate::Ysc2_512SpongeStreamCipher),
+//! [`Ysc2_1024SpongeStreamCipher`](crate::Ysc2_1024SpongeStreamCipher) or the AuxCrypt
+//! streams.
+//!
+//! [`EncryptingWriter`] and [`DecryptingReader`] start at the cipher's current
+//! keystream position wherever the underlying stream is, e.g. after a
+//! plaintext header; seeking them keeps that offset between stream and
+//! keystream. For [`CipherFile`], byte `i` of the file is always processed
+//! with keystream byte `i`. Either way random access stays correct.
+
+use cipher::{StreamCipher, StreamCipherSeek};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+/// 한 번의 `write` 호출에서 암호화하는 최대 바이트 수
+const CHUNK_BYTES: usize = 4096;
+
+fn keystream_exhausted() -> io::Error {
+    io::Error::other("stream cipher keystream exhausted")
+}
+
+fn seek_out_of_range() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, "offset is beyond the stream cipher keystream")
+}
+
+/// 암호 위치를 바이트 오프셋 `pos`로 옮깁니다.
+fn seek_cipher<C: StreamCipherSeek>(cipher: &mut C, pos: u128) -> io::Result<()> {
+    cipher.try_seek(pos).map_err(|_| seek_out_of_range())
+}
+
+/// `inner`를 옮기고, 스트림 위치와 키스트림 위치의 차이 `base`를 유지하도록
+/// 암호 위치를 맞춥니다.
+///
+/// `base`는 첫 탐색 때 기록합니다. 그때까지는 읽기/쓰기가 두 위치를 함께
+/// 전진시키므로, 감쌀 때의 차이와 같습니다.
+fn seek_relative<S: Seek, C: StreamCipherSeek>(
+    inner: &mut S,
+    cipher: &mut C,
+    base: &mut Option<i128>,
+    pos: SeekFrom,
+) -> io::Result<u64> {
+    let base = match *base {
+        Some(base) => base,
+        None => {
+            let stream_pos = inner.stream_position()?;
+            let cipher_pos: u128 = cipher.try_current_pos().map_err(|_| seek_out_of_range())?;
+            let cipher_pos = i128::try_from(cipher_pos).map_err(|_| seek_out_of_range())?;
+            *base.insert(i128::from(stream_pos) - cipher_pos)
+        }
+    };
+    let pos = inner.seek(pos)?;
+    let offset = u128::try_from(i128::from(pos) - base).map_err(|_| seek_out_of_range())?;
+    seek_cipher(cipher, offset)?;
+    Ok(pos)
+}
+
+/// `buf`의 앞부분을 암호화해 `inner`에 한 번 씁니다.
+///
+/// 내부 writer가 일부만 받아들이면, 쓰지 못한 바이트만큼 암호 위치를 되돌려
+/// 다음 호출이 같은 키스트림을 쓰도록 합니다.
+fn encrypt_write<W: Write, C: StreamCipher + StreamCipherSeek>(
+    inner: &mut W,
+    cipher: &mut C,
+    buf: &[u8],
+) -> io::Result<usize> {
+    let mut chunk = [0u8; CHUNK_BYTES];
+    let len = buf.len().min(CHUNK_BYTES);
+    let start: u128 = cipher.current_pos();
+    chunk[..len].copy_from_slice(&buf[..len]);
+    cipher.try_apply_keystream(&mut chunk[..len]).map_err(|_| keystream_exhausted())?;
+
+    let written = match inner.write(&chunk[..len]) {
+        Ok(written) => written,
+        Err(err) => {
+            cipher.seek(start);
+            return Err(err);
+        }
+    };
+    if written < len {
+        cipher.seek(start + written as u128);
+    }
+    Ok(written)
+}
+
+/// `inner`에서 한 번 읽은 바이트를 복호화해 `buf`의 앞부분에 둡니다.
+///
+/// 키스트림을 먼저 만들어 두므로, 키스트림이 모자라면 `inner`에서 아무것도
+/// 읽지 않고 실패합니다. 내부 reader가 일부만 채우면, 쓰지 않은 키스트림만큼
+/// 암호 위치를 되돌립니다.
+fn decrypt_read<R: Read, C: StreamCipher + StreamCipherSeek>(
+    inner: &mut R,
+    cipher: &mut C,
+    buf: &mut [u8],
+) -> io::Result<usize> {
+    let mut keystream = [0u8; CHUNK_BYTES];
+    let len = buf.len().min(CHUNK_BYTES);
+    let start: u128 = cipher.current_pos();
+    cipher.try_apply_keystream(&mut keystream[..len]).map_err(|_| keystream_exhausted())?;
+
+    let read = match inner.read(&mut buf[..len]) {
+        Ok(read) => read,
+        Err(err) => {
+            cipher.seek(start);
+            return Err(err);
+        }
+    };
+    if read < len {
+        cipher.seek(start + read as u128);
+    }
+    for (byte, key) in buf[..read].iter_mut().zip(&keystream) {
+        *byte ^= key;
+    }
+    Ok(read)
+}
+
+/// A writer that encrypts everything written to it before passing it on.
+pub struct EncryptingWriter<W: Write, C: StreamCipher + StreamCipherSeek> {
+    inner: W,
+    cipher: C,
+    base: Option<i128>,
+}
+
+impl<W: Write, C: StreamCipher + StreamCipherSeek> EncryptingWriter<W, C> {
+    /// Wraps `inner`; the next byte written uses the cipher's current keystream position.
+    ///
+    /// Seeking keeps the offset between `inner` and the keystream that holds
+    /// at this point, so `inner` need not be at its start.
+    pub fn new(inner: W, cipher: C) -> Self {
+        Self { inner, cipher, base: None }
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// Writing to it directly desynchronises the keystream position.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns the underlying writer and the cipher.
+    pub fn into_inner(self) -> (W, C) {
+        (self.inner, self.cipher)
+    }
+}
+
+impl<W: Write, C: StreamCipher + StreamCipherSeek> Write for EncryptingWriter<W, C> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        encrypt_write(&mut self.inner, &mut self.cipher, buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<W: Write + Seek, C: StreamCipher + StreamCipherSeek> Seek for EncryptingWriter<W, C> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        seek_relative(&mut self.inner, &mut self.cipher, &mut self.base, pos)
+    }
+}
+
+/// A reader that decrypts everything read through it.
+///
+/// As with any stream cipher, decryption and encryption are the same
+/// operation, so this also encrypts a plaintext source.
+pub struct DecryptingReader<R: Read, C: StreamCipher + StreamCipherSeek> {
+    inner: R,
+    cipher: C,
+    base: Option<i128>,
+}
+
+impl<R: Read, C: StreamCipher + StreamCipherSeek> DecryptingReader<R, C> {
+    /// Wraps `inner`; the next byte read uses the cipher's current keystream position.
+    ///
+    /// Seeking keeps the offset between `inner` and the keystream that holds
+    /// at this point, so `inner` need not be at its start.
+    pub fn new(inner: R, cipher: C) -> Self {
+        Self { inner, cipher, base: None }
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// Reading from it directly desynchronises the keystream position.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns the underlying reader and the cipher.
+    pub fn into_inner(self) -> (R, C) {
+        (self.inner, self.cipher)
+    }
+}
+
+impl<R: Read, C: StreamCipher + StreamCipherSeek> Read for DecryptingReader<R, C> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        decrypt_read(&mut self.inner, &mut self.cipher, buf)
+    }
+}
+
+impl<R: Read + Seek, C: StreamCipher + StreamCipherSeek> Seek for DecryptingReader<R, C> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        seek_relative(&mut self.inner, &mut self.cipher, &mut self.base, pos)
+    }
+}
+
+/// An encrypted file (or any `Read + Write + Seek` store) with plaintext
+/// random access.
+///
+/// Reads decrypt and writes encrypt at the current offset, and byte `i` of
+/// the file always uses keystream byte `i`, so regions can be read or
+/// overwritten in any order.
+///
+/// Overwriting a region under the same key and nonce reuses its keystream;
+/// an observer of both versions learns the XOR of the old and new plaintext.
+pub struct CipherFile<F: Read + Write + Seek, C: StreamCipher + StreamCipherSeek> {
+    inner: F,
+    cipher: C,
+}
+
+impl<F: Read + Write + Seek, C: StreamCipher + StreamCipherSeek> CipherFile<F, C> {
+    /// Wraps `inner`, seeking the cipher to its current position.
+    pub fn new(mut inner: F, mut cipher: C) -> io::Result<Self> {
+        let pos = inner.stream_position()?;
+        seek_cipher(&mut cipher, pos.into())?;
+        Ok(Self { inner, cipher })
+    }
+
+    /// Returns a reference to the underlying file.
+    pub fn get_ref(&self) -> &F {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying file.
+    ///
+    /// Seeking it directly desynchronises the keystream position.
+    pub fn get_mut(&mut self) -> &mut F {
+        &mut self.inner
+    }
+
+    /// Returns the underlying file and the cipher.
+    pub fn into_inner(self) -> (F, C) {
+        (self.inner, self.cipher)
+    }
+}
+
+impl<F: Read + Write + Seek, C: StreamCipher + StreamCipherSeek> Read for CipherFile<F, C> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        decrypt_read(&mut self.inner, &mut self.cipher, buf)
+    }
+}
+
+impl<F: Read + Write + Seek, C: StreamCipher + StreamCipherSeek> Write for CipherFile<F, C> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        encrypt_write(&mut self.inner, &mut self.cipher, buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<F: Read + Write + Seek, C: StreamCipher + StreamCipherSeek> Seek for CipherFile<F, C> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let pos = self.inner.seek(pos)?;
+        seek_cipher(&mut self.cipher, pos.into())?;
+        Ok(pos)
+    }
+}
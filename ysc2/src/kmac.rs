@@ -0,0 +1,161 @@
+//======================================================================
+// src/kmac.rs
+// KMAC 방식의 키 MAC / 키 XOF (사용자 정의 해시 위에 구성)
+//======================================================================
+
+//! KMAC-style message authentication over [`Ysc2CustomHasher`] (NIST SP 800-185).
+//!
+//! With key `K`, customization string `S` and output length `L` bits:
+//!
+//! ```text
+//! Ysc2Kmac(K, X, L, S)    = Custom(bytepad(encode_string(K), 64) || X || right_encode(L), L, "KMAC", S)
+//! Ysc2KmacXof(K, X, L, S) = Custom(bytepad(encode_string(K), 64) || X || right_encode(0), L, "KMAC", S)
+//! ```
+//!
+//! `L` is part of the input, so requesting a different length gives an
+//! unrelated tag rather than a prefix of a longer one. The XOF variant encodes
+//! `L` as zero, so its output can be read to any length.
+
+use crate::consts::{MIN_KEY_BYTES, RATE_BYTES};
+use crate::custom::{bytepad, encode_string, right_encode, Ysc2CustomHasher};
+use crate::sponge::Reader;
+use crate::variant::Ysc2Variant;
+use core::marker::PhantomData;
+use digest::{
+    consts::U64,
+    core_api::XofReaderCoreWrapper,
+    generic_array::ArrayLength,
+    crypto_common::KeySizeUser,
+    ExtendableOutput, FixedOutput, InvalidLength, Key, KeyInit, MacMarker, Output, OutputSizeUser,
+    Update, XofReader,
+};
+use zeroize::Zeroize;
+
+/// Function name of the KMAC constructions.
+const FUNCTION_NAME: &[u8] = b"KMAC";
+
+/// 키를 `bytepad(encode_string(K), 64)`로 흡수한 사용자 정의 해셔를 만듭니다.
+fn keyed<V: Ysc2Variant>(key: &[u8], customization: &[u8]) -> Result<Ysc2CustomHasher<V>, InvalidLength> {
+    if key.len() < MIN_KEY_BYTES {
+        return Err(InvalidLength);
+    }
+    let mut hasher = Ysc2CustomHasher::new(FUNCTION_NAME, customization);
+    let mut encoded_key = encode_string(key);
+    let mut padded_key = bytepad(&encoded_key, RATE_BYTES);
+    hasher.update(&padded_key);
+    encoded_key.zeroize();
+    padded_key.zeroize();
+    Ok(hasher)
+}
+
+/// Output length in bits, as bound into the computation.
+fn bit_length(bytes: usize) -> u64 {
+    (bytes as u64).checked_mul(8).expect("output length overflows 64 bits")
+}
+
+/// KMAC-style MAC with an `N`-byte tag (64 bytes by default).
+///
+/// Keys may have any length from [`MIN_KEY_BYTES`] up (see
+/// [`KeyInit::new_from_slice`]); there is no unkeyed constructor.
+#[derive(Clone)]
+pub struct Ysc2Kmac<V: Ysc2Variant, N: ArrayLength<u8> + 'static = U64> {
+    inner: Ysc2CustomHasher<V>,
+    _output: PhantomData<N>,
+}
+
+impl<V: Ysc2Variant, N: ArrayLength<u8> + 'static> Ysc2Kmac<V, N> {
+    /// Creates a MAC for `key` with the customization string `customization`.
+    ///
+    /// # Errors
+    /// Returns [`InvalidLength`] if `key` is shorter than [`MIN_KEY_BYTES`].
+    pub fn new_customized(key: &[u8], customization: &[u8]) -> Result<Self, InvalidLength> {
+        Ok(Self { inner: keyed(key, customization)?, _output: PhantomData })
+    }
+
+    /// Writes a tag of `out.len()` bytes, with that length bound into the
+    /// computation, instead of the `N`-byte tag.
+    pub fn finalize_variable(mut self, out: &mut [u8]) {
+        self.inner.update(&right_encode(bit_length(out.len())));
+        self.inner.finalize_xof().read(out);
+    }
+}
+
+impl<V: Ysc2Variant, N: ArrayLength<u8> + 'static> KeySizeUser for Ysc2Kmac<V, N> {
+    type KeySize = V::KeySize;
+}
+
+impl<V: Ysc2Variant, N: ArrayLength<u8> + 'static> KeyInit for Ysc2Kmac<V, N> {
+    fn new(key: &Key<Self>) -> Self {
+        Self::new_customized(key, b"").expect("variant keys are longer than MIN_KEY_BYTES")
+    }
+
+    /// Accepts keys of any length from [`MIN_KEY_BYTES`] up.
+    fn new_from_slice(key: &[u8]) -> Result<Self, InvalidLength> {
+        Self::new_customized(key, b"")
+    }
+}
+
+impl<V: Ysc2Variant, N: ArrayLength<u8> + 'static> MacMarker for Ysc2Kmac<V, N> {}
+
+impl<V: Ysc2Variant, N: ArrayLength<u8> + 'static> Update for Ysc2Kmac<V, N> {
+    fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+}
+
+impl<V: Ysc2Variant, N: ArrayLength<u8> + 'static> OutputSizeUser for Ysc2Kmac<V, N> {
+    type OutputSize = N;
+}
+
+impl<V: Ysc2Variant, N: ArrayLength<u8> + 'static> FixedOutput for Ysc2Kmac<V, N> {
+    fn finalize_into(self, out: &mut Output<Self>) {
+        self.finalize_variable(out);
+    }
+}
+
+/// KMAC-style keyed XOF: the output length is not bound, so it can be read
+/// to any length.
+#[derive(Clone)]
+pub struct Ysc2KmacXof<V: Ysc2Variant> {
+    inner: Ysc2CustomHasher<V>,
+}
+
+impl<V: Ysc2Variant> Ysc2KmacXof<V> {
+    /// Creates a keyed XOF for `key` with the customization string `customization`.
+    ///
+    /// # Errors
+    /// Returns [`InvalidLength`] if `key` is shorter than [`MIN_KEY_BYTES`].
+    pub fn new_customized(key: &[u8], customization: &[u8]) -> Result<Self, InvalidLength> {
+        Ok(Self { inner: keyed(key, customization)? })
+    }
+}
+
+impl<V: Ysc2Variant> KeySizeUser for Ysc2KmacXof<V> {
+    type KeySize = V::KeySize;
+}
+
+impl<V: Ysc2Variant> KeyInit for Ysc2KmacXof<V> {
+    fn new(key: &Key<Self>) -> Self {
+        Self::new_customized(key, b"").expect("variant keys are longer than MIN_KEY_BYTES")
+    }
+
+    /// Accepts keys of any length from [`MIN_KEY_BYTES`] up.
+    fn new_from_slice(key: &[u8]) -> Result<Self, InvalidLength> {
+        Self::new_customized(key, b"")
+    }
+}
+
+impl<V: Ysc2Variant> Update for Ysc2KmacXof<V> {
+    fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+}
+
+impl<V: Ysc2Variant> ExtendableOutput for Ysc2KmacXof<V> {
+    type Reader = XofReaderCoreWrapper<Reader<V>>;
+
+    fn finalize_xof(mut self) -> Self::Reader {
+        self.inner.update(&right_encode(0));
+        self.inner.finalize_xof()
+    }
+}
@@ -1,9 +1,6 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![doc = include_str!("../README.md")]
 
-#![cfg(feature = "ysc2_simd")]
-#![feature(portable_simd)]
-
 //======================================================================
 // src/lib.rs
 // 크레이트의 진입점. 공개 API를 선언하고 모듈을 구성합니다.
@@ -12,23 +9,71 @@
 
 // --- Module declarations ---
 pub mod stream;
-use crate::stream::Ysc2StreamCore;
+use crate::stream::{Ysc2LegacyStreamCore, Ysc2SpongeStreamCore};
+
+pub mod block;
+
+pub mod xysc2;
+
+pub mod io;
+
+#[cfg(feature = "auxcrypt")]
+pub mod cascade;
+#[cfg(feature = "auxcrypt")]
+pub mod dual;
 
 #[cfg(feature = "ysc2x")]
 pub mod sponge;
+#[cfg(feature = "ysc2x")]
+pub mod custom;
+#[cfg(feature = "ysc2x")]
+pub mod kmac;
+#[cfg(feature = "ysc2x")]
+pub mod tuple;
+#[cfg(feature = "ysc2x")]
+pub mod parallel;
+#[cfg(feature = "ysc2x")]
+pub mod tree;
 
 pub mod variant;
 use crate::variant::{Ysc2_1024, Ysc2_512};
 
 pub mod consts;
 mod backends;
+pub use backends::{Backend, BackendUnavailable};
+
+pub mod permutation;
+pub use permutation::{Ysc2Permutation, Ysc2State};
+
+pub mod generic;
+
+#[cfg(feature = "circuit")]
+pub mod circuit;
+#[cfg(feature = "circuit")]
+pub mod cost;
 
 #[cfg(feature = "ysc2x")]
 pub mod aead;
 
 // --- Convenience Type Aliases for Users ---
-pub type Ysc2_512StreamCipher = cipher::StreamCipherCoreWrapper<Ysc2StreamCore<Ysc2_512>>;
-pub type Ysc2_1024StreamCipher = cipher::StreamCipherCoreWrapper<Ysc2StreamCore<Ysc2_1024>>;
+pub type Ysc2_512SpongeStreamCipher = cipher::StreamCipherCoreWrapper<Ysc2SpongeStreamCore<Ysc2_512>>;
+pub type Ysc2_1024SpongeStreamCipher = cipher::StreamCipherCoreWrapper<Ysc2SpongeStreamCore<Ysc2_1024>>;
+/// The original stream construction under its original name, so existing code keeps its keystream.
+#[deprecated(note = "recoverable from one known keystream block; use `Ysc2_512SpongeStreamCipher`, or `Ysc2_512LegacyStreamCipher` for old data")]
+pub type Ysc2_512StreamCipher = Ysc2_512LegacyStreamCipher;
+/// The original stream construction under its original name, so existing code keeps its keystream.
+#[deprecated(note = "recoverable from one known keystream block; use `Ysc2_1024SpongeStreamCipher`, or `Ysc2_1024LegacyStreamCipher` for old data")]
+pub type Ysc2_1024StreamCipher = Ysc2_1024LegacyStreamCipher;
+/// The original stream construction, which outputs the whole permuted state; only for existing data.
+pub type Ysc2_512LegacyStreamCipher = cipher::StreamCipherCoreWrapper<Ysc2LegacyStreamCore<Ysc2_512>>;
+/// The original stream construction, which outputs the whole permuted state; only for existing data.
+pub type Ysc2_1024LegacyStreamCipher = cipher::StreamCipherCoreWrapper<Ysc2LegacyStreamCore<Ysc2_1024>>;
+/// Stream cipher with 128-byte nonces, safe to draw at random.
+pub type XYsc2_512StreamCipher = xysc2::XYsc2StreamCipher<Ysc2_512>;
+/// Stream cipher with 128-byte nonces, safe to draw at random.
+pub type XYsc2_1024StreamCipher = xysc2::XYsc2StreamCipher<Ysc2_1024>;
+pub type Ysc2_512BlockCipher = block::Ysc2BlockCipher<Ysc2_512>;
+pub type Ysc2_1024BlockCipher = block::Ysc2BlockCipher<Ysc2_1024>;
 
 // --- Test Module ---
 #[cfg(test)]
@@ -47,17 +92,71 @@ pub type Ysc2_1024Hasher = sponge::Hasher<Ysc2_1024>;
 pub type Ysc2_512Hash = sponge::Hash<Ysc2_512>;
 #[cfg(feature = "ysc2x")]
 pub type Ysc2_1024Hash = sponge::Hash<Ysc2_1024>;
+/// The keyed hash: same type as [`Ysc2_512Hash`], fixed-size keys and 64-byte tags.
+/// Prefer [`Ysc2_512Kmac`] for new protocols.
 #[cfg(feature = "ysc2x")]
 pub type Ysc2_512Mac = sponge::Hash<Ysc2_512>;
+/// The keyed hash: same type as [`Ysc2_1024Hash`], fixed-size keys and 64-byte tags.
+/// Prefer [`Ysc2_1024Kmac`] for new protocols.
 #[cfg(feature = "ysc2x")]
 pub type Ysc2_1024Mac = sponge::Hash<Ysc2_1024>;
 #[cfg(feature = "ysc2x")]
 pub type Ysc2_512XofReader = sponge::Reader<Ysc2_512>;
 #[cfg(feature = "ysc2x")]
 pub type Ysc2_1024XofReader = sponge::Reader<Ysc2_1024>;
+#[cfg(feature = "ysc2x")]
+pub type Ysc2_512CustomHasher = custom::Ysc2CustomHasher<Ysc2_512>;
+#[cfg(feature = "ysc2x")]
+pub type Ysc2_1024CustomHasher = custom::Ysc2CustomHasher<Ysc2_1024>;
+#[cfg(feature = "ysc2x")]
+pub type Ysc2_512Kmac = kmac::Ysc2Kmac<Ysc2_512>;
+#[cfg(feature = "ysc2x")]
+pub type Ysc2_1024Kmac = kmac::Ysc2Kmac<Ysc2_1024>;
+#[cfg(feature = "ysc2x")]
+pub type Ysc2_512KmacXof = kmac::Ysc2KmacXof<Ysc2_512>;
+#[cfg(feature = "ysc2x")]
+pub type Ysc2_1024KmacXof = kmac::Ysc2KmacXof<Ysc2_1024>;
+#[cfg(feature = "ysc2x")]
+pub type Ysc2_512TupleHash = tuple::Ysc2TupleHash<Ysc2_512>;
+#[cfg(feature = "ysc2x")]
+pub type Ysc2_1024TupleHash = tuple::Ysc2TupleHash<Ysc2_1024>;
+#[cfg(feature = "ysc2x")]
+pub type Ysc2_512TupleHashXof = tuple::Ysc2TupleHashXof<Ysc2_512>;
+#[cfg(feature = "ysc2x")]
+pub type Ysc2_1024TupleHashXof = tuple::Ysc2TupleHashXof<Ysc2_1024>;
+#[cfg(feature = "ysc2x")]
+pub type Ysc2_512ParallelHash = parallel::Ysc2ParallelHash<Ysc2_512>;
+#[cfg(feature = "ysc2x")]
+pub type Ysc2_1024ParallelHash = parallel::Ysc2ParallelHash<Ysc2_1024>;
+#[cfg(feature = "ysc2x")]
+pub type Ysc2_512ParallelHashXof = parallel::Ysc2ParallelHashXof<Ysc2_512>;
+#[cfg(feature = "ysc2x")]
+pub type Ysc2_1024ParallelHashXof = parallel::Ysc2ParallelHashXof<Ysc2_1024>;
+#[cfg(feature = "ysc2x")]
+pub type Ysc2_512TreeHasher = tree::Ysc2TreeHasher<Ysc2_512>;
+#[cfg(feature = "ysc2x")]
+pub type Ysc2_1024TreeHasher = tree::Ysc2TreeHasher<Ysc2_1024>;
 
 // -- AEAD Aliases --
 #[cfg(feature = "ysc2x")]
 pub type Ysc2_512Aead = aead::Ysc2Aead<Ysc2_512>;
 #[cfg(feature = "ysc2x")]
-pub type Ysc2_1024Aead = aead::Ysc2Aead<Ysc2_1024>;
\ No newline at end of file
+pub type Ysc2_1024Aead = aead::Ysc2Aead<Ysc2_1024>;
+#[cfg(feature = "ysc2x")]
+pub type XYsc2_512Aead = xysc2::XYsc2Aead<Ysc2_512>;
+#[cfg(feature = "ysc2x")]
+pub type XYsc2_1024Aead = xysc2::XYsc2Aead<Ysc2_1024>;
+
+// -- Cascade Aliases (YSC2 + AuxCrypt) --
+#[cfg(feature = "auxcrypt")]
+pub type Cascade512StreamCipher = cascade::CascadeStreamCipher<Ysc2_512, auxcrypt::variant::AuxCrypt512V3>;
+#[cfg(feature = "auxcrypt")]
+pub type Cascade1024StreamCipher = cascade::CascadeStreamCipher<Ysc2_1024, auxcrypt::variant::AuxCrypt1024V3>;
+#[cfg(all(feature = "auxcrypt", feature = "ysc2x"))]
+pub type Cascade512Aead = cascade::CascadeAead<Ysc2_512, auxcrypt::variant::AuxCrypt512V3>;
+#[cfg(all(feature = "auxcrypt", feature = "ysc2x"))]
+pub type Cascade1024Aead = cascade::CascadeAead<Ysc2_1024, auxcrypt::variant::AuxCrypt1024V3>;
+#[cfg(feature = "auxcrypt")]
+pub type DualPermutation512Hasher = dual::DualPermutationHasher<Ysc2_512, auxcrypt::variant::AuxCrypt512V3>;
+#[cfg(feature = "auxcrypt")]
+pub type DualPermutation1024Hasher = dual::DualPermutationHasher<Ysc2_1024, auxcrypt::variant::AuxCrypt1024V3>;
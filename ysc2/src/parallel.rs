@@ -0,0 +1,162 @@
+//======================================================================
+// src/parallel.rs
+// 입력을 고정 크기 블록으로 나누어 따로 해시한 뒤 결합하는 ParallelHash 방식
+//======================================================================
+
+//! ParallelHash-style hashing of long inputs (NIST SP 800-185).
+//!
+//! The input `X` is split into blocks `X0, ..., X(n-1)` of `B` bytes (the
+//! last may be shorter). Each block is hashed on its own with the plain
+//! [`Hasher`] to a 64-byte chaining value `Zi`, and the chaining values are
+//! combined as
+//!
+//! ```text
+//! Ysc2ParallelHash(X, B, L, S)    = Custom(left_encode(B) || Z0 || ... || Z(n-1) || right_encode(n) || right_encode(L), L, "ParallelHash", S)
+//! Ysc2ParallelHashXof(X, B, L, S) = Custom(left_encode(B) || Z0 || ... || Z(n-1) || right_encode(n) || right_encode(0), L, "ParallelHash", S)
+//! ```
+//!
+//! The result depends on `B` but not on how the input is split across
+//! `update` calls. The blocks are hashed one after another here; they are
+//! independent, so callers may hash them elsewhere and get the same result.
+
+use crate::custom::{left_encode, right_encode, Ysc2CustomHasher};
+use crate::sponge::{Hasher, Reader};
+use crate::variant::Ysc2Variant;
+use core::marker::PhantomData;
+use digest::{
+    consts::U64, core_api::XofReaderCoreWrapper, generic_array::ArrayLength, ExtendableOutput, FixedOutput,
+    HashMarker, Output, OutputSizeUser, Update, XofReader,
+};
+
+/// Function name of the ParallelHash constructions.
+const FUNCTION_NAME: &[u8] = b"ParallelHash";
+
+/// Length of a block chaining value in bytes.
+const CHAINING_VALUE_BYTES: usize = 64;
+
+/// 블록 분할과 체이닝 값 흡수를 담당하는 공통 상태.
+#[derive(Clone)]
+struct BlockState<V: Ysc2Variant> {
+    outer: Ysc2CustomHasher<V>,
+    block: Hasher<V>,
+    block_size: usize,
+    filled: usize,
+    blocks: u64,
+}
+
+impl<V: Ysc2Variant> BlockState<V> {
+    fn new(block_size: usize, customization: &[u8]) -> Self {
+        assert!(block_size > 0, "ParallelHash block size must be non-zero");
+        let mut outer = Ysc2CustomHasher::new(FUNCTION_NAME, customization);
+        outer.update(&left_encode(block_size as u64));
+        Self { outer, block: Hasher::default(), block_size, filled: 0, blocks: 0 }
+    }
+
+    fn update(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            let take = data.len().min(self.block_size - self.filled);
+            let (head, rest) = data.split_at(take);
+            self.block.update(head);
+            self.filled += take;
+            data = rest;
+            if self.filled == self.block_size {
+                self.end_block();
+            }
+        }
+    }
+
+    /// 현재 블록의 체이닝 값을 바깥 해셔에 흡수합니다.
+    fn end_block(&mut self) {
+        let mut chaining_value = [0u8; CHAINING_VALUE_BYTES];
+        core::mem::take(&mut self.block).finalize_xof().read(&mut chaining_value);
+        self.outer.update(&chaining_value);
+        self.filled = 0;
+        self.blocks += 1;
+    }
+
+    /// 남은 블록을 마무리하고 `right_encode(n) || right_encode(output_bits)`를 흡수합니다.
+    fn finish(mut self, output_bits: u64) -> XofReaderCoreWrapper<Reader<V>> {
+        if self.filled > 0 {
+            self.end_block();
+        }
+        self.outer.update(&right_encode(self.blocks));
+        self.outer.update(&right_encode(output_bits));
+        self.outer.finalize_xof()
+    }
+}
+
+/// ParallelHash-style hash with an `N`-byte output (64 bytes by default).
+#[derive(Clone)]
+pub struct Ysc2ParallelHash<V: Ysc2Variant, N: ArrayLength<u8> + 'static = U64> {
+    state: BlockState<V>,
+    _output: PhantomData<N>,
+}
+
+impl<V: Ysc2Variant, N: ArrayLength<u8> + 'static> Ysc2ParallelHash<V, N> {
+    /// Creates a hash with `block_size`-byte blocks and the customization
+    /// string `customization`.
+    ///
+    /// # Panics
+    /// Panics if `block_size` is zero.
+    pub fn new(block_size: usize, customization: &[u8]) -> Self {
+        Self { state: BlockState::new(block_size, customization), _output: PhantomData }
+    }
+
+    /// Writes a hash of `out.len()` bytes, with that length bound into the
+    /// computation, instead of the `N`-byte hash.
+    pub fn finalize_variable(self, out: &mut [u8]) {
+        self.state.finish(out.len() as u64 * 8).read(out);
+    }
+}
+
+impl<V: Ysc2Variant, N: ArrayLength<u8> + 'static> HashMarker for Ysc2ParallelHash<V, N> {}
+
+impl<V: Ysc2Variant, N: ArrayLength<u8> + 'static> Update for Ysc2ParallelHash<V, N> {
+    fn update(&mut self, data: &[u8]) {
+        self.state.update(data);
+    }
+}
+
+impl<V: Ysc2Variant, N: ArrayLength<u8> + 'static> OutputSizeUser for Ysc2ParallelHash<V, N> {
+    type OutputSize = N;
+}
+
+impl<V: Ysc2Variant, N: ArrayLength<u8> + 'static> FixedOutput for Ysc2ParallelHash<V, N> {
+    fn finalize_into(self, out: &mut Output<Self>) {
+        self.finalize_variable(out);
+    }
+}
+
+/// ParallelHash-style XOF: the output length is not bound, so it can be read
+/// to any length.
+#[derive(Clone)]
+pub struct Ysc2ParallelHashXof<V: Ysc2Variant> {
+    state: BlockState<V>,
+}
+
+impl<V: Ysc2Variant> Ysc2ParallelHashXof<V> {
+    /// Creates a XOF with `block_size`-byte blocks and the customization
+    /// string `customization`.
+    ///
+    /// # Panics
+    /// Panics if `block_size` is zero.
+    pub fn new(block_size: usize, customization: &[u8]) -> Self {
+        Self { state: BlockState::new(block_size, customization) }
+    }
+}
+
+impl<V: Ysc2Variant> HashMarker for Ysc2ParallelHashXof<V> {}
+
+impl<V: Ysc2Variant> Update for Ysc2ParallelHashXof<V> {
+    fn update(&mut self, data: &[u8]) {
+        self.state.update(data);
+    }
+}
+
+impl<V: Ysc2Variant> ExtendableOutput for Ysc2ParallelHashXof<V> {
+    type Reader = XofReaderCoreWrapper<Reader<V>>;
+
+    fn finalize_xof(self) -> Self::Reader {
+        self.state.finish(0)
+    }
+}
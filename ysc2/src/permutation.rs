@@ -0,0 +1,131 @@
+//======================================================================
+// src/permutation.rs
+// 자체 구성(스펀지, 듀플렉스, Even-Mansour 등)을 위한 공개 순열 API
+//======================================================================
+
+use crate::backends;
+use crate::consts::{STATE_BYTES, STATE_WORDS};
+use crate::variant::Ysc2Variant;
+use core::marker::PhantomData;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// The raw YSC2 permutation over a 1024-bit state.
+///
+/// The number of rounds is fixed by the variant `V` (`V::ROUNDS`). Words are
+/// mapped to and from bytes in little-endian order, as in every other YSC2 mode.
+pub struct Ysc2Permutation<V: Ysc2Variant>(PhantomData<V>);
+
+impl<V: Ysc2Variant> Ysc2Permutation<V> {
+    /// Number of rounds applied by [`Self::permute`].
+    pub const ROUNDS: usize = V::ROUNDS;
+
+    /// Applies the permutation to a word-oriented state in place.
+    #[inline]
+    pub fn permute(state: &mut [u64; STATE_WORDS]) {
+        backends::permutation::<V>(state);
+    }
+
+    /// Applies the permutation to a byte-oriented state in place.
+    pub fn permute_bytes(bytes: &mut [u8; STATE_BYTES]) {
+        let mut state = Ysc2State::from_bytes(bytes);
+        Self::permute(&mut state.words);
+        *bytes = state.to_bytes();
+    }
+
+    /// Applies the inverse permutation to a word-oriented state in place.
+    #[inline]
+    pub fn permute_inverse(state: &mut [u64; STATE_WORDS]) {
+        backends::permutation_inverse::<V>(state);
+    }
+
+    /// Applies the inverse permutation to a byte-oriented state in place.
+    pub fn permute_inverse_bytes(bytes: &mut [u8; STATE_BYTES]) {
+        let mut state = Ysc2State::from_bytes(bytes);
+        Self::permute_inverse(&mut state.words);
+        *bytes = state.to_bytes();
+    }
+}
+
+/// A 1024-bit permutation state with byte-level access.
+///
+/// Byte `i` of the state is byte `i % 8` (little-endian) of word `i / 8`.
+#[derive(Clone, Default, PartialEq, Eq, Zeroize, ZeroizeOnDrop)]
+pub struct Ysc2State {
+    words: [u64; STATE_WORDS],
+}
+
+impl Ysc2State {
+    /// Creates an all-zero state.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a state from its word representation.
+    pub fn from_words(words: [u64; STATE_WORDS]) -> Self {
+        Self { words }
+    }
+
+    /// Creates a state from its byte representation.
+    pub fn from_bytes(bytes: &[u8; STATE_BYTES]) -> Self {
+        let mut state = Self::new();
+        for (word, chunk) in state.words.iter_mut().zip(bytes.chunks_exact(8)) {
+            *word = u64::from_le_bytes(chunk.try_into().unwrap());
+        }
+        state
+    }
+
+    /// Returns the byte representation of the state.
+    pub fn to_bytes(&self) -> [u8; STATE_BYTES] {
+        let mut bytes = [0u8; STATE_BYTES];
+        for (chunk, word) in bytes.chunks_exact_mut(8).zip(self.words.iter()) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Returns the state words.
+    pub fn words(&self) -> &[u64; STATE_WORDS] {
+        &self.words
+    }
+
+    /// Returns the state words mutably.
+    pub fn words_mut(&mut self) -> &mut [u64; STATE_WORDS] {
+        &mut self.words
+    }
+
+    /// XORs `data` into the state starting at byte `offset`.
+    ///
+    /// # Panics
+    /// Panics if `offset + data.len()` exceeds the state size (128 bytes).
+    pub fn xor_in(&mut self, offset: usize, data: &[u8]) {
+        assert!(offset + data.len() <= STATE_BYTES, "data does not fit in the state");
+        for (i, byte) in data.iter().enumerate() {
+            let pos = offset + i;
+            self.words[pos / 8] ^= (*byte as u64) << (8 * (pos % 8));
+        }
+    }
+
+    /// Copies state bytes starting at byte `offset` into `out`.
+    ///
+    /// # Panics
+    /// Panics if `offset + out.len()` exceeds the state size (128 bytes).
+    pub fn extract(&self, offset: usize, out: &mut [u8]) {
+        assert!(offset + out.len() <= STATE_BYTES, "output does not fit in the state");
+        for (i, byte) in out.iter_mut().enumerate() {
+            let pos = offset + i;
+            *byte = (self.words[pos / 8] >> (8 * (pos % 8))) as u8;
+        }
+    }
+
+    /// Applies the YSC2 permutation of variant `V` to the state.
+    #[inline]
+    pub fn permute<V: Ysc2Variant>(&mut self) {
+        Ysc2Permutation::<V>::permute(&mut self.words);
+    }
+
+    /// Applies the inverse of the permutation of variant `V` to the state.
+    #[inline]
+    pub fn permute_inverse<V: Ysc2Variant>(&mut self) {
+        Ysc2Permutation::<V>::permute_inverse(&mut self.words);
+    }
+}
@@ -6,7 +6,7 @@
 
 use cipher::KeySizeUser;
 use digest::HashMarker;
-use crate::backends;
+use crate::backends::{self, Backend, LANES};
 use crate::consts::{RATE_BYTES, STATE_WORDS};
 use crate::variant::Ysc2Variant;
 use core::marker::PhantomData;
@@ -33,6 +33,55 @@ pub struct Ysc2xCore<V: Ysc2Variant> {
 }
 
 impl<V: Ysc2Variant> Ysc2xCore<V> {
+    /// 용량 영역(바이트 64..)에 도메인 구분자를 실은 빈 코어를 만듭니다.
+    /// 같은 입력이라도 일반 해시와 다른 출력을 냅니다.
+    pub(crate) fn with_capacity_domain(domain: &str) -> Self {
+        let domain = domain.as_bytes();
+        assert!(domain.len() <= RATE_BYTES, "domain separator does not fit in the capacity");
+        let mut core = Self::default();
+        for (i, byte) in domain.iter().enumerate() {
+            core.state[STATE_WORDS / 2 + i / 8] ^= (*byte as u64) << (8 * (i % 8));
+        }
+        core
+    }
+
+    /// `self`에서 시작하는 해셔 `LANES`개에 같은 길이(64바이트 배수)의 입력을 하나씩
+    /// 흡수하고 마무리한 뒤, 각 해셔의 첫 출력 블록을 돌려줍니다.
+    /// 상태들을 한꺼번에 순열하므로 `backend`의 병렬 경로를 씁니다.
+    pub(crate) fn finalize_x4(
+        &self,
+        backend: Backend,
+        inputs: [&[u8]; LANES],
+    ) -> [[u8; RATE_BYTES]; LANES] {
+        let len = inputs[0].len();
+        assert!(len.is_multiple_of(RATE_BYTES) && inputs.iter().all(|input| input.len() == len));
+        let mut states = [self.state; LANES];
+        for offset in (0..len).step_by(RATE_BYTES) {
+            for (state, input) in states.iter_mut().zip(inputs) {
+                for (word, chunk) in state.iter_mut().zip(input[offset..offset + RATE_BYTES].chunks_exact(8)) {
+                    *word ^= u64::from_le_bytes(chunk.try_into().unwrap());
+                }
+            }
+            backend.permute_x4::<V>(&mut states);
+        }
+        // 남은 데이터가 없으므로 패딩 블록은 0x80 한 바이트뿐입니다.
+        for state in states.iter_mut() {
+            state[0] ^= 0x80;
+        }
+        backend.permute_x4::<V>(&mut states);
+        // Reader::read_block과 같이 순열 후 rate 부분을 내보냅니다.
+        backend.permute_x4::<V>(&mut states);
+        let out = states.map(|state| {
+            let mut block = [0u8; RATE_BYTES];
+            for (chunk, word) in block.chunks_exact_mut(8).zip(state) {
+                chunk.copy_from_slice(&word.to_le_bytes());
+            }
+            block
+        });
+        states.zeroize();
+        out
+    }
+
     fn absorb_block(&mut self, block: &Block<Self>) {
         for (i, chunk) in block.chunks_exact(8).enumerate() {
             self.state[i] ^= u64::from_le_bytes(chunk.try_into().unwrap());
@@ -41,20 +90,24 @@ impl<V: Ysc2Variant> Ysc2xCore<V> {
     }
 
     fn new_from_raw<const N: usize>(inputs: [&[u8]; N]) -> Self {
-        let flattend = inputs.concat();
+        let mut flattend = inputs.concat();
         let mut core = Self::default();
         let (raw_blocks, rem) = flattend.as_chunks::<64>();
-        let buffer = raw_blocks.iter().map(|raw_block| {
-            return Block::<Self>::clone_from_slice(raw_block);
+        let mut buffer = raw_blocks.iter().map(|raw_block| {
+            Block::<Self>::clone_from_slice(raw_block)
         }).collect::<Vec<Block<Self>>>();
         core.update_blocks(&buffer);
+        // 키가 담긴 임시 버퍼를 지웁니다.
+        buffer.iter_mut().for_each(|block| block.as_mut_slice().zeroize());
         // finalize_xof_core를 호출하지는 않으므로, 수동으로 마지막 블록을 처리합니다.
         if !rem.is_empty() {
              let mut padded_block = Block::<Self>::default();
              padded_block[..rem.len()].copy_from_slice(rem);
              padded_block[rem.len()] = 0x80;
              core.absorb_block(&padded_block);
+             padded_block.as_mut_slice().zeroize();
         }
+        flattend.zeroize();
         core
     }
 }
@@ -100,6 +153,7 @@ impl<V: Ysc2Variant> ExtendableOutputCore for Ysc2xCore<V> {
         padded_block[final_block.len()] = 0x80; // Simple 10*1 padding
 
         self.absorb_block(&padded_block);
+        padded_block.as_mut_slice().zeroize();
 
         Reader {
             state: self.state,
@@ -167,11 +221,14 @@ pub type Hasher<V> = CoreWrapper<Ysc2xCore<V>>;
 pub type Hash<V> = CoreWrapper<FixedOutputCoreWrapper<V>>;
 
 /// 고정 길이 출력을 위해 Ysc2xCore를 한번 더 감싸는 래퍼.
+/// 내부 코어가 drop될 때 상태가 지워집니다.
 #[derive(Clone)]
 pub struct FixedOutputCoreWrapper<V: Ysc2Variant>(Ysc2xCore<V>);
 
 impl<V: Ysc2Variant> HashMarker for FixedOutputCoreWrapper<V> {}
 
+impl<V: Ysc2Variant> ZeroizeOnDrop for FixedOutputCoreWrapper<V> {}
+
 impl<V: Ysc2Variant> MacMarker for FixedOutputCoreWrapper<V> {}
 
 impl<V: Ysc2Variant> Default for FixedOutputCoreWrapper<V> {
@@ -209,5 +266,6 @@ impl<V: Ysc2Variant> FixedOutputCore for FixedOutputCoreWrapper<V> {
     fn finalize_fixed_core(&mut self, buffer: &mut Buffer<Self>, out: &mut Output<Self>) {
         let mut reader = self.0.finalize_xof_core(buffer);
         *out = reader.read_block();
+        // Reader는 drop될 때 지워집니다.
     }
 }
\ No newline at end of file
@@ -4,61 +4,375 @@
 //======================================================================
 
 use cipher::{
-    BlockSizeUser, Iv, IvSizeUser, Key, KeyIvInit, KeySizeUser, StreamCipherCore, StreamCipherSeekCore
+    BlockSizeUser, Counter, InvalidLength, Iv, IvSizeUser, Key, KeyIvInit, KeySizeUser, OverflowError,
+    StreamCipherCore, StreamCipherSeekCore
 };
 use core::marker::PhantomData;
+use zeroize::{Zeroize, ZeroizeOnDrop};
 use crate::backends;
+use crate::consts::{MIN_KEY_BYTES, RATE_BYTES, STATE_WORDS};
+use crate::variant::{Revision, Ysc2Variant};
 
-use crate::variant::Ysc2Variant;
+/// Revision of the stream construction built on top of the permutation.
+pub trait StreamRevision: Sized + Clone {
+    /// Whether a keystream block is squeezed from the rate of the permuted
+    /// state (see [`Sponge`]) instead of being the whole permuted state, so
+    /// that a known keystream block no longer inverts directly to the keyed
+    /// state.
+    const RATE_OUTPUT: bool;
 
+    /// Whether key and nonce are absorbed through the domain-separated
+    /// initialisation phase (see [`stream_iv`]) instead of being loaded raw.
+    const DOMAIN_SEPARATED_INIT: bool;
+}
+
+/// The original stream construction: a keystream block is `P(state ^ counter)`.
+///
+/// Since the permutation is public and invertible, one block of known
+/// plaintext reveals the keyed state and thus the whole keystream for that
+/// key and nonce. Kept only to decrypt existing data.
+#[derive(Clone)]
+pub struct Legacy;
+impl StreamRevision for Legacy {
+    const RATE_OUTPUT: bool = false;
+    const DOMAIN_SEPARATED_INIT: bool = false;
+}
+
+/// The sponge stream construction: with `x` the state with the counter XORed
+/// into each of its eight rate words, a keystream block is
+/// `rate(P(x)) || rate(P(P(x)))`.
+///
+/// The counter goes into every rate word because `P` only mixes word `i` with
+/// word `i + 8`. The state is keyed through the domain-separated
+/// initialisation phase.
+///
+/// # Security
+///
+/// The capacity words never leave the core, but they only protect each pair
+/// `(i, i + 8)` on its own: the permutation is eight independent 128-bit
+/// permutations, and a block shows both rate words a pair takes. Guessing the
+/// capacity word of a pair in `P(x)`, permuting once more and checking against
+/// the second half of the block pins that pair down, and one inversion then
+/// gives the keyed words. One known block thus gives up the whole keyed state
+/// with about `8 · 2^64` permutation calls, far below the key size.
+#[derive(Clone)]
+pub struct Sponge;
+impl StreamRevision for Sponge {
+    const RATE_OUTPUT: bool = true;
+    const DOMAIN_SEPARATED_INIT: bool = true;
+}
+
+/// 모드 식별자: 스트림 암호
+const MODE_STREAM: u64 = 1;
+/// 모드 식별자: 확장 Nonce 하위 키 유도 ([`xysc2`](crate::xysc2))
+pub(crate) const MODE_SUBKEY: u64 = 2;
+/// 모드 식별자: 가변 길이 키/Nonce 스트림 암호
+const MODE_STREAM_VARIABLE: u64 = 3;
+/// 모드 식별자: 가변 길이 키 AEAD
+#[cfg_attr(not(feature = "ysc2x"), allow(dead_code))]
+pub(crate) const MODE_AEAD_VARIABLE: u64 = 4;
+/// 모드 식별자: 캐스케이드 암호의 YSC2 하위 키 유도
+#[cfg_attr(not(feature = "auxcrypt"), allow(dead_code))]
+pub(crate) const MODE_CASCADE_KEY: u64 = 5;
+/// 초기화 단계 구분값 (상태의 마지막 워드에 XOR)
+pub(crate) const INIT_PHASE_KEY: u64 = 1;
+pub(crate) const INIT_PHASE_NONCE: u64 = 2;
+/// 초기화 단계 구분값: AEAD의 가변 길이 Nonce
+#[cfg_attr(not(feature = "ysc2x"), allow(dead_code))]
+pub(crate) const INIT_PHASE_AEAD_NONCE: u64 = 3;
+
+/// The initial capacity half (words 8..16) of a [`Sponge`] stream state.
+///
+/// Words 8..14 hold `V::STREAM_DOMAIN`, zero-padded to 48 bytes. Word 14 binds
+/// the parameters: key bits (bits 0..16), nonce bits (16..32), rounds (32..48),
+/// permutation revision (48..56) and the mode (56..64, `1` for the stream
+/// cipher, `2` for the [`xysc2`](crate::xysc2) subkey, `3` for
+/// [`Ysc2KeystreamCore::new_variable`], `4` for `Ysc2Aead::new_variable` and
+/// `5` for the cascade cipher's YSC2 subkey). Word 15 starts at zero and receives the phase values.
+///
+/// # Panics
+/// Panics if `V::STREAM_DOMAIN` is longer than 48 bytes or `V::ROUNDS` does
+/// not fit in 16 bits.
+pub fn stream_iv<V: Ysc2Variant>() -> [u64; 8] {
+    init_iv::<V>(MODE_STREAM)
+}
+
+/// [`stream_iv`]의 모드 필드만 바꾼 초기 용량 워드입니다.
+pub(crate) fn init_iv<V: Ysc2Variant>(mode: u64) -> [u64; 8] {
+    let domain = V::STREAM_DOMAIN.as_bytes();
+    assert!(domain.len() <= 48, "stream domain separator is longer than 48 bytes");
+    assert!(V::ROUNDS < 1 << 16, "round count does not fit the 16-bit IV field");
+    let mut padded = [0u8; 48];
+    padded[..domain.len()].copy_from_slice(domain);
+
+    let revision: u64 = match V::REVISION {
+        Revision::V1 => 1,
+        Revision::V2 => 2,
+    };
+    let mut iv = [0u64; 8];
+    for (word, chunk) in iv.iter_mut().zip(padded.chunks_exact(8)) {
+        *word = u64::from_le_bytes(chunk.try_into().unwrap());
+    }
+    iv[6] = (V::KEY_SIZE as u64 * 8)
+        | (V::NONCE_SIZE as u64 * 8) << 16
+        | (V::ROUNDS as u64) << 32
+        | revision << 48
+        | mode << 56;
+    iv
+}
+
+/// 바이트 블록을 상태의 앞부분(rate)에 XOR합니다.
+fn xor_rate(state: &mut [u64; STATE_WORDS], block: &[u8]) {
+    for (word, chunk) in state.iter_mut().zip(block.chunks_exact(8)) {
+        *word ^= u64::from_le_bytes(chunk.try_into().unwrap());
+    }
+}
+
+/// 스폰지 리비전 초기화: 상수를 싣고 키와 Nonce를 64바이트 블록 단위로 흡수합니다.
+pub(crate) fn init_state<V: Ysc2Variant>(mode: u64, key: &[u8], nonce: &[u8]) -> [u64; STATE_WORDS] {
+    let mut state = [0u64; STATE_WORDS];
+    state[8..].copy_from_slice(&init_iv::<V>(mode));
+
+    for block in key.chunks(RATE_BYTES) {
+        xor_rate(&mut state, block);
+        state[15] ^= INIT_PHASE_KEY;
+        backends::permutation::<V>(&mut state);
+    }
+    for block in nonce.chunks(RATE_BYTES) {
+        xor_rate(&mut state, block);
+        state[15] ^= INIT_PHASE_NONCE;
+        backends::permutation::<V>(&mut state);
+    }
+    state
+}
+
+/// 흡수를 마친 상태의 rate에서 `out`을 짜내고 상태를 지웁니다.
+/// rate만 내보내므로 숨은 용량 때문에 출력으로부터 입력을 되돌릴 수 없습니다.
+pub(crate) fn squeeze_key<V: Ysc2Variant>(mut state: [u64; STATE_WORDS], out: &mut [u8]) {
+    for (i, block) in out.chunks_mut(RATE_BYTES).enumerate() {
+        if i > 0 {
+            backends::permutation::<V>(&mut state);
+        }
+        for (chunk, word) in block.chunks_mut(8).zip(state.iter()) {
+            chunk.copy_from_slice(&word.to_le_bytes()[..chunk.len()]);
+        }
+    }
+    state.zeroize();
+}
+
+/// 길이(64비트 LE)를 앞에 붙인 입력을 64바이트 블록 단위로 흡수합니다.
+/// 마지막 블록은 0으로 채우며, 길이 접두어 덕분에 인코딩이 단사입니다.
+pub(crate) fn absorb_length_prefixed<V: Ysc2Variant>(state: &mut [u64; STATE_WORDS], phase: u64, data: &[u8]) {
+    let len = (data.len() as u64).to_le_bytes();
+    let mut encoded = len.iter().chain(data).copied();
+    let mut block = [0u8; RATE_BYTES];
+    loop {
+        block.fill(0);
+        let mut filled = 0;
+        for (b, byte) in block.iter_mut().zip(encoded.by_ref()) {
+            *b = byte;
+            filled += 1;
+        }
+        if filled == 0 {
+            break;
+        }
+        xor_rate(state, &block);
+        state[15] ^= phase;
+        backends::permutation::<V>(state);
+        if filled < RATE_BYTES {
+            break;
+        }
+    }
+    block.zeroize();
+}
+
+/// 가변 길이 초기화: 길이를 붙인 키와 Nonce를 흡수합니다.
+fn variable_init_state<V: Ysc2Variant>(key: &[u8], nonce: &[u8]) -> [u64; STATE_WORDS] {
+    let mut state = [0u64; STATE_WORDS];
+    state[8..].copy_from_slice(&init_iv::<V>(MODE_STREAM_VARIABLE));
+    absorb_length_prefixed::<V>(&mut state, INIT_PHASE_KEY, key);
+    absorb_length_prefixed::<V>(&mut state, INIT_PHASE_NONCE, nonce);
+    state
+}
+
+/// 원래(레거시) 초기화: 키와 Nonce를 상수 없이 그대로 싣고 순열을 한 번 적용합니다.
+fn legacy_init_state<V: Ysc2Variant>(key: &[u8], iv: &[u8]) -> [u64; STATE_WORDS] {
+    let mut state = [0u64; STATE_WORDS];
+
+    // 키와 Nonce를 초기 상태에 로드합니다.
+    if V::KEY_SIZE == 128 { // 1024비트 키
+        for (i, chunk) in key.chunks_exact(8).enumerate() {
+            state[i] = u64::from_le_bytes(chunk.try_into().unwrap());
+        }
+        for (i, chunk) in iv.chunks_exact(8).enumerate() {
+            // Nonce는 상태의 후반부와 XOR합니다.
+            state[i + 8] ^= u64::from_le_bytes(chunk.try_into().unwrap());
+        }
+    } else { // 512비트 키
+        for (i, chunk) in key.chunks_exact(8).enumerate() {
+            state[i] = u64::from_le_bytes(chunk.try_into().unwrap());
+        }
+        for (i, chunk) in iv.chunks_exact(8).enumerate() {
+            state[i + 8] = u64::from_le_bytes(chunk.try_into().unwrap());
+        }
+    }
+
+    // 2. Run the permutation for INIT_ROUNDS.
+    backends::permutation::<V>(&mut state);
+    state
+}
 
-pub struct Ysc2StreamCore<V: Ysc2Variant> {
+/// Number of keystream blocks available for one key and nonce.
+///
+/// The block at position `p` XORs the 64-bit counter value `(p + 1) mod 2^64`
+/// into the state, so positions `0..KEYSTREAM_BLOCKS` are all distinct.
+pub const KEYSTREAM_BLOCKS: u128 = 1 << 64;
+
+/// A keystream block position, the counter of [`StreamCipherSeekCore`].
+///
+/// Conversions from integers reject positions at or past [`KEYSTREAM_BLOCKS`],
+/// so `try_seek` to an offset with no keystream left returns an error instead
+/// of panicking. The position after the last block can still be read back.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BlockPos(u128);
+
+impl TryFrom<u128> for BlockPos {
+    type Error = OverflowError;
+
+    fn try_from(pos: u128) -> Result<Self, OverflowError> {
+        if pos < KEYSTREAM_BLOCKS { Ok(BlockPos(pos)) } else { Err(OverflowError) }
+    }
+}
+
+impl From<BlockPos> for u128 {
+    fn from(pos: BlockPos) -> u128 {
+        pos.0
+    }
+}
+
+/// 나머지 정수형과 `BlockPos` 사이의 변환 (`u128`을 거칩니다).
+macro_rules! impl_block_pos_conversions {
+    ($($int:ty),*) => {$(
+        impl TryFrom<$int> for BlockPos {
+            type Error = OverflowError;
+
+            fn try_from(pos: $int) -> Result<Self, OverflowError> {
+                u128::try_from(pos).map_err(|_| OverflowError)?.try_into()
+            }
+        }
+
+        impl TryFrom<BlockPos> for $int {
+            type Error = OverflowError;
+
+            fn try_from(pos: BlockPos) -> Result<Self, OverflowError> {
+                <$int>::try_from(pos.0).map_err(|_| OverflowError)
+            }
+        }
+    )*};
+}
+
+impl_block_pos_conversions!(i32, u32, u64, usize);
+
+impl Counter for BlockPos {}
+
+/// The YSC2 stream cipher core of variant `V` and stream revision `R`.
+///
+/// Use it through [`Ysc2SpongeStreamCore`]; [`Ysc2LegacyStreamCore`] is only for
+/// data produced by the original construction.
+///
+/// The keystream ends after [`KEYSTREAM_BLOCKS`] blocks: `try_apply_keystream`
+/// then fails instead of reusing keystream. Block positions are `u128`, so
+/// byte offsets up to the end of the keystream can be sought with `u128`.
+///
+/// Seeking to or past the end returns an error (see [`BlockPos`]).
+///
+/// # Panics
+/// Generating a block beyond the end through the core directly panics.
+///
+/// The keyed state is wiped on drop.
+#[derive(Zeroize, ZeroizeOnDrop)]
+pub struct Ysc2KeystreamCore<V: Ysc2Variant, R: StreamRevision> {
     /// The 1024-bit internal state (16 x 64-bit words).
     pub(crate) state: [u64; 16],
-    /// The 64-bit block counter.
-    pub(crate) counter: u64,
-    /// PhantomData to associate the core with a specific `Ysc2Variant`.
-    pub(crate) _variant: PhantomData<V>,
+    /// The block position: the number of keystream blocks consumed so far.
+    pub(crate) counter: u128,
+    /// PhantomData to associate the core with a specific `Ysc2Variant` and revision.
+    pub(crate) _variant: PhantomData<(V, R)>,
 }
 
-impl<V: Ysc2Variant> KeySizeUser for Ysc2StreamCore<V> {
+/// YSC2 stream cipher core with keystream blocks squeezed from the rate (see [`Sponge`]).
+pub type Ysc2SpongeStreamCore<V> = Ysc2KeystreamCore<V, Sponge>;
+
+/// The original YSC2 stream cipher core, which outputs the whole permuted state (see [`Legacy`]).
+pub type Ysc2LegacyStreamCore<V> = Ysc2KeystreamCore<V, Legacy>;
+
+/// The original YSC2 stream cipher core.
+#[deprecated(note = "recoverable from one known keystream block; use `Ysc2SpongeStreamCore`, or `Ysc2LegacyStreamCore` for old data")]
+pub type Ysc2StreamCore<V> = Ysc2LegacyStreamCore<V>;
+
+impl<V: Ysc2Variant, R: StreamRevision> Ysc2KeystreamCore<V, R> {
+    /// 다음 블록의 카운터 값을 돌려주고 위치를 한 블록 전진시킵니다.
+    #[inline(always)]
+    pub(crate) fn next_block_counter(&mut self) -> u64 {
+        assert!(self.counter < KEYSTREAM_BLOCKS, "YSC2 keystream exhausted");
+        self.counter += 1;
+        self.counter as u64
+    }
+
+    /// `backend`으로 키스트림 클로저를 실행합니다 (선택 상태와 무관).
+    pub(crate) fn process_with(&mut self, backend: backends::Backend, f: impl cipher::StreamClosure<BlockSize = cipher::consts::U128>) {
+        match backend {
+            #[cfg(all(feature = "ysc2_simd", target_arch = "x86_64"))]
+            backends::Backend::Simd => f.call(&mut backends::simd::Backend(self)),
+            _ => f.call(&mut backends::soft::Backend(self)),
+        }
+    }
+}
+
+impl<V: Ysc2Variant> Ysc2SpongeStreamCore<V> {
+    /// Creates a core from a key of any length from [`MIN_KEY_BYTES`] up and a
+    /// nonce of any length.
+    ///
+    /// Both are absorbed with their lengths, so e.g. a 32-byte key and its
+    /// zero-extension give unrelated keystreams. This path is separate from
+    /// [`KeyIvInit::new`] (which stays the fast path for `V::KEY_SIZE` and
+    /// `V::NONCE_SIZE` inputs) and never agrees with it.
+    ///
+    /// # Errors
+    /// Returns [`InvalidLength`] if `key` is shorter than [`MIN_KEY_BYTES`].
+    pub fn new_variable(key: &[u8], nonce: &[u8]) -> Result<Self, InvalidLength> {
+        if key.len() < MIN_KEY_BYTES {
+            return Err(InvalidLength);
+        }
+        Ok(Self {
+            state: variable_init_state::<V>(key, nonce),
+            counter: 0,
+            _variant: PhantomData,
+        })
+    }
+}
+
+impl<V: Ysc2Variant, R: StreamRevision> KeySizeUser for Ysc2KeystreamCore<V, R> {
     type KeySize = V::KeySize;
 }
 
-impl<V: Ysc2Variant> IvSizeUser for Ysc2StreamCore<V> {
+impl<V: Ysc2Variant, R: StreamRevision> IvSizeUser for Ysc2KeystreamCore<V, R> {
     type IvSize = V::NonceSize;
 }
 
-impl<V: Ysc2Variant> BlockSizeUser for Ysc2StreamCore<V> {
+impl<V: Ysc2Variant, R: StreamRevision> BlockSizeUser for Ysc2KeystreamCore<V, R> {
     type BlockSize = cipher::consts::U128; // 1024-bit blocks
 }
 
-impl<V: Ysc2Variant> KeyIvInit for Ysc2StreamCore<V> {
-    /// Creates a new `Ysc2StreamCore` instance, initializing its state with the
+impl<V: Ysc2Variant, R: StreamRevision> KeyIvInit for Ysc2KeystreamCore<V, R> {
+    /// Creates a new `Ysc2KeystreamCore` instance, initializing its state with the
     /// given key and nonce according to the specification.
     fn new(key: &Key<Self>, iv: &Iv<Self>) -> Self {
-        let mut state = [0u64; 16];
-        
-        // 키와 Nonce를 초기 상태에 로드합니다.
-        if V::KEY_SIZE == 128 { // 1024비트 키
-            for (i, chunk) in key.chunks_exact(8).enumerate() {
-                state[i] = u64::from_le_bytes(chunk.try_into().unwrap());
-            }
-            for (i, chunk) in iv.chunks_exact(8).enumerate() {
-                // Nonce는 상태의 후반부와 XOR합니다.
-                state[i + 8] ^= u64::from_le_bytes(chunk.try_into().unwrap());
-            }
-        } else { // 512비트 키
-            for (i, chunk) in key.chunks_exact(8).enumerate() {
-                state[i] = u64::from_le_bytes(chunk.try_into().unwrap());
-            }
-            for (i, chunk) in iv.chunks_exact(8).enumerate() {
-                state[i + 8] = u64::from_le_bytes(chunk.try_into().unwrap());
-            }
-        }
-
-        // 2. Run the permutation for INIT_ROUNDS.
-        backends::permutation::<V>(&mut state);
+        let state = if R::DOMAIN_SEPARATED_INIT {
+            init_state::<V>(MODE_STREAM, key, iv)
+        } else {
+            legacy_init_state::<V>(key, iv)
+        };
         Self {
             state,
             counter: 0,
@@ -67,28 +381,31 @@ impl<V: Ysc2Variant> KeyIvInit for Ysc2StreamCore<V> {
     }
 }
 
-impl<V: Ysc2Variant> StreamCipherCore for Ysc2StreamCore<V> {
+impl<V: Ysc2Variant, R: StreamRevision> StreamCipherCore for Ysc2KeystreamCore<V, R> {
+    /// Blocks left before the keystream is exhausted, saturated to `usize::MAX`
+    /// (no slice can need more blocks than that).
     fn remaining_blocks(&self) -> Option<usize> {
-        None
+        let remaining = KEYSTREAM_BLOCKS.saturating_sub(self.counter);
+        Some(usize::try_from(remaining).unwrap_or(usize::MAX))
     }
-    
+
     /// Processes data by applying the keystream, delegating the core permutation
-    /// to the backend selected at compile time.
+    /// to the backend selected at runtime.
     fn process_with_backend(&mut self, f: impl cipher::StreamClosure<BlockSize = Self::BlockSize>) {
-        f.call(&mut backends::Backend(self));
+        self.process_with(backends::Backend::active(), f);
     }
 }
 
-impl<V: Ysc2Variant> StreamCipherSeekCore for Ysc2StreamCore<V> {
-    type Counter = u64;
+impl<V: Ysc2Variant, R: StreamRevision> StreamCipherSeekCore for Ysc2KeystreamCore<V, R> {
+    type Counter = BlockPos;
 
     /// Gets the current block position (counter).
     fn get_block_pos(&self) -> Self::Counter {
-        self.counter
+        BlockPos(self.counter)
     }
 
     /// Sets the block position (counter).
     fn set_block_pos(&mut self, pos: Self::Counter) {
-        self.counter = pos;
+        self.counter = pos.0;
     }
 }
\ No newline at end of file
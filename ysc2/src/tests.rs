@@ -3,9 +3,6 @@
 //======================================================================
 #![cfg(test)]
 
-extern crate alloc;
-use alloc::vec;
-
 #[cfg(feature = "ysc2x")]
 use aead::AeadInPlace;
 
@@ -15,18 +12,19 @@ use crate::{
     Ysc2_512Hash, Ysc2_512Hasher
 };
 use crate::{
-    Ysc2_1024StreamCipher, Ysc2_512StreamCipher,
+    Ysc2_1024SpongeStreamCipher, Ysc2_512LegacyStreamCipher, Ysc2_512SpongeStreamCipher,
 };
 #[cfg(feature = "ysc2x")]
-use aead::{Aead, KeyInit};
+use aead::KeyInit;
+use auxcrypt::test_support::{wiped_on_drop, KeystreamInto};
 use cipher::{KeyIvInit, StreamCipher, StreamCipherSeek};
 #[cfg(feature = "ysc2x")]
-use digest::{ExtendableOutput, KeyInit as MacKeyInit, Mac as _, Update, XofReader};
+use digest::{ExtendableOutput, Update, XofReader};
 
 // --- WASM Compatibility Fix ---
 // Replace OsRng with a deterministic PRNG for reproducible tests.
 #[cfg(feature = "ysc2x")]
-use rand_core::{RngCore, SeedableRng};
+use rand_core::SeedableRng;
 #[cfg(feature = "ysc2x")]
 use rand_chacha::ChaCha8Rng;
 
@@ -47,12 +45,12 @@ fn ysc2_512_stream_roundtrip() {
     let mut buffer = PLAINTEXT.to_vec();
 
     // Encrypt
-    let mut cipher = Ysc2_512StreamCipher::new(&key, &nonce);
+    let mut cipher = Ysc2_512SpongeStreamCipher::new(&key, &nonce);
     cipher.apply_keystream(&mut buffer);
     assert_ne!(buffer, PLAINTEXT, "Ciphertext should not match plaintext");
 
     // Decrypt
-    let mut cipher = Ysc2_512StreamCipher::new(&key, &nonce);
+    let mut cipher = Ysc2_512SpongeStreamCipher::new(&key, &nonce);
     cipher.apply_keystream(&mut buffer);
     assert_eq!(buffer, PLAINTEXT, "Decrypted text should match original");
 }
@@ -61,21 +59,1069 @@ fn ysc2_512_stream_roundtrip() {
 fn ysc2_1024_stream_seek_consistency() {
     let key = [0x03u8; 128].into();
     let nonce = [0x04u8; 64].into();
-    let mut buffer1 = vec![0u8; 128];
-    let mut buffer2 = vec![0u8; 128];
+    let mut buffer1 = [0u8; 128];
+    let mut buffer2 = [0u8; 128];
 
     // Generate 2 blocks of keystream at once
-    let mut cipher1 = Ysc2_1024StreamCipher::new(&key, &nonce);
+    let mut cipher1 = Ysc2_1024SpongeStreamCipher::new(&key, &nonce);
     cipher1.apply_keystream(&mut buffer1);
 
     // Generate the second block separately after seeking
-    let mut cipher2 = Ysc2_1024StreamCipher::new(&key, &nonce);
+    let mut cipher2 = Ysc2_1024SpongeStreamCipher::new(&key, &nonce);
     cipher2.seek(64); // Seek to the beginning of the second 64-byte part
     cipher2.apply_keystream(&mut buffer2[64..]);
 
     assert_eq!(buffer1[64..], buffer2[64..], "Keystream from sought position should match");
 }
 
+#[test]
+fn ysc2_stream_exhaustion_and_deep_seek() {
+    use crate::generic::{self, PlainWords};
+    use crate::stream::KEYSTREAM_BLOCKS;
+    use crate::variant::Ysc2_512;
+
+    let key = [0x07u8; 64];
+    let nonce = [0x08u8; 64];
+    let state = generic::stream_init_sponge::<Ysc2_512, _>(&mut PlainWords, &[0x0707_0707_0707_0707; 8], &[0x0808_0808_0808_0808; 8]);
+    let last_blocks: Vec<u8> = [u64::MAX, 0]
+        .iter()
+        .flat_map(|counter| generic::keystream_block_sponge::<Ysc2_512, _>(&mut PlainWords, &state, counter))
+        .flat_map(u64::to_le_bytes)
+        .collect();
+
+    // u64 바이트 오프셋으로는 닿지 않는 마지막 두 블록 안쪽으로 이동합니다.
+    let offset = (KEYSTREAM_BLOCKS - 2) * 128 + 5;
+    let mut cipher = Ysc2_512SpongeStreamCipher::new(&key.into(), &nonce.into());
+    cipher.seek(offset);
+    assert_eq!(cipher.current_pos::<u128>(), offset);
+
+    let mut tail = [0u8; 251];
+    cipher.try_apply_keystream(&mut tail).expect("the last blocks should be available");
+    assert_eq!(tail[..], last_blocks[5..], "The last blocks should use counters 2^64 - 1 and 0");
+    assert_eq!(cipher.current_pos::<u128>(), KEYSTREAM_BLOCKS * 128);
+    assert!(cipher.try_apply_keystream(&mut [0u8; 1]).is_err(), "An exhausted keystream should not wrap");
+
+    // 끝을 넘는 길이는 아무것도 적용하지 않고 거부합니다.
+    let mut cipher = Ysc2_512SpongeStreamCipher::new(&key.into(), &nonce.into());
+    cipher.seek((KEYSTREAM_BLOCKS - 1) * 128);
+    let mut data = [0u8; 129];
+    assert!(cipher.try_apply_keystream(&mut data).is_err());
+    assert_eq!(data, [0u8; 129], "A rejected call should leave the data untouched");
+    cipher.try_apply_keystream(&mut data[..128]).expect("one block should remain");
+
+    // 끝이나 그 너머로의 탐색은 패닉 없이 거부되고 위치를 바꾸지 않습니다.
+    let mut cipher = Ysc2_512SpongeStreamCipher::new(&key.into(), &nonce.into());
+    cipher.seek(300u64);
+    for target in [KEYSTREAM_BLOCKS * 128, KEYSTREAM_BLOCKS * 128 + 5, u128::MAX] {
+        assert!(cipher.try_seek(target).is_err(), "Seeking to {target} should fail");
+    }
+    assert_eq!(cipher.current_pos::<u128>(), 300);
+}
+
+#[test]
+fn xysc2_stream_uses_derived_subkey() {
+    use crate::stream::Ysc2SpongeStreamCore;
+    use crate::variant::Ysc2_512;
+    use crate::xysc2::derive_subkey;
+    use crate::XYsc2_512StreamCipher;
+
+    let key = [0x09u8; 64].into();
+    let mut nonce = [0u8; 128];
+    nonce[..64].fill(0x0A);
+    nonce[64..].fill(0x0B);
+
+    let mut keystream = [0u8; 256];
+    XYsc2_512StreamCipher::new(&key, &nonce.into()).apply_keystream(&mut keystream);
+
+    // 앞부분으로 유도한 하위 키와 뒷부분 Nonce로 돌린 기본 스트림과 같아야 합니다.
+    let subkey = derive_subkey::<Ysc2_512>(&key, nonce[..64].try_into().unwrap());
+    assert_ne!(subkey, key, "The subkey should differ from the master key");
+    let mut expected = [0u8; 256];
+    cipher::StreamCipherCoreWrapper::<Ysc2SpongeStreamCore<Ysc2_512>>::new(&subkey, nonce[64..].into())
+        .apply_keystream(&mut expected);
+    assert_eq!(keystream, expected);
+
+    // 앞부분의 한 비트만 달라도 다른 하위 키가 됩니다.
+    let mut other_nonce = nonce;
+    other_nonce[63] ^= 1;
+    let mut other = [0u8; 256];
+    XYsc2_512StreamCipher::new(&key, &other_nonce.into()).apply_keystream(&mut other);
+    assert_ne!(keystream, other, "Nonce prefixes should select different subkeys");
+}
+
+#[test]
+fn ysc2_variable_length_key_and_nonce() {
+    use crate::stream::Ysc2SpongeStreamCore;
+    use crate::variant::Ysc2_512;
+    type Core = Ysc2SpongeStreamCore<Ysc2_512>;
+
+    let keystream = |core: Core| {
+        let mut out = [0u8; 128];
+        cipher::StreamCipherCoreWrapper::from_core(core).apply_keystream(&mut out);
+        out
+    };
+    let key = [0x0Cu8; 32];
+    let nonce = [0x0Du8; 12];
+    let base = keystream(Core::new_variable(&key, &nonce).unwrap());
+
+    let mut padded_key = [0u8; 64];
+    padded_key[..32].copy_from_slice(&key);
+    assert_ne!(base, keystream(Core::new_variable(&padded_key, &nonce).unwrap()), "Key length should be bound");
+    assert_ne!(base, keystream(Core::new_variable(&key, &[0x0D; 16]).unwrap()), "Nonce length should be bound");
+    assert_ne!(base, keystream(Core::new_variable(&key, &[]).unwrap()));
+    assert_ne!(
+        keystream(Core::new_variable(&[0x0E; 64], &[0x0F; 64]).unwrap()),
+        keystream(Core::new(&[0x0E; 64].into(), &[0x0F; 64].into())),
+        "The variable-length path should be separate from the fixed-size one"
+    );
+
+    assert!(Core::new_variable(&[0u8; 15], &nonce).is_err(), "Keys under 16 bytes should be rejected");
+    assert!(Core::new_variable(&[0u8; 16], &nonce).is_ok());
+}
+
+/// 한 번에 최대 7바이트만 받아들이는 writer (부분 쓰기 검사용)
+struct TrickleWriter(Vec<u8>);
+
+impl std::io::Write for TrickleWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = buf.len().min(7);
+        self.0.extend_from_slice(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn ysc2_io_writer_and_reader_roundtrip() {
+    use crate::io::{DecryptingReader, EncryptingWriter};
+    use std::io::{Read, Seek, SeekFrom, Write};
+
+    let key = [0x10u8; 128].into();
+    let nonce = [0x11u8; 64].into();
+    let plaintext: Vec<u8> = (0..5000u32).map(|i| (i * 31 % 251) as u8).collect();
+    let mut expected = plaintext.clone();
+    Ysc2_1024SpongeStreamCipher::new(&key, &nonce).apply_keystream(&mut expected);
+
+    // 부분 쓰기가 일어나도 키스트림 위치가 어긋나지 않아야 합니다.
+    let mut writer = EncryptingWriter::new(TrickleWriter(Vec::new()), Ysc2_1024SpongeStreamCipher::new(&key, &nonce));
+    writer.write_all(&plaintext[..1234]).unwrap();
+    writer.write_all(&plaintext[1234..]).unwrap();
+    let (ciphertext, _) = writer.into_inner();
+    assert_eq!(ciphertext.0, expected, "Writer output should match the one-shot keystream");
+
+    let mut reader = DecryptingReader::new(std::io::Cursor::new(&expected), Ysc2_1024SpongeStreamCipher::new(&key, &nonce));
+    let mut decrypted = Vec::new();
+    reader.read_to_end(&mut decrypted).unwrap();
+    assert_eq!(decrypted, plaintext);
+
+    let mut tail = [0u8; 100];
+    reader.seek(SeekFrom::Start(3000)).unwrap();
+    reader.read_exact(&mut tail).unwrap();
+    assert_eq!(tail[..], plaintext[3000..3100], "Reads after a seek should decrypt correctly");
+}
+
+#[test]
+fn ysc2_io_seek_after_plaintext_header() {
+    use crate::io::{DecryptingReader, EncryptingWriter};
+    use std::io::{Cursor, Read, Seek, SeekFrom, Write};
+
+    let key = [0x1Au8; 64].into();
+    let nonce = [0x1Bu8; 64].into();
+    let header = b"HEADER-v1\n";
+    let plaintext: Vec<u8> = (0..700u32).map(|i| (i * 7 % 253) as u8).collect();
+
+    // 평문 헤더 뒤에서 감싸면 헤더 다음 바이트가 키스트림 0번 바이트를 씁니다.
+    let mut cursor = Cursor::new(header.to_vec());
+    cursor.seek(SeekFrom::End(0)).unwrap();
+    let mut writer = EncryptingWriter::new(cursor, Ysc2_512SpongeStreamCipher::new(&key, &nonce));
+    writer.write_all(&plaintext).unwrap();
+    writer.seek(SeekFrom::Start(header.len() as u64 + 200)).unwrap();
+    writer.write_all(&plaintext[200..300]).unwrap();
+    let (cursor, _) = writer.into_inner();
+    let file = cursor.into_inner();
+    assert_eq!(file[..header.len()], header[..]);
+    let mut expected = plaintext.clone();
+    Ysc2_512SpongeStreamCipher::new(&key, &nonce).apply_keystream(&mut expected);
+    assert_eq!(file[header.len()..], expected[..], "Seeking should keep the offset of the header");
+
+    let mut cursor = Cursor::new(&file);
+    cursor.seek(SeekFrom::Start(header.len() as u64)).unwrap();
+    let mut reader = DecryptingReader::new(cursor, Ysc2_512SpongeStreamCipher::new(&key, &nonce));
+    let mut part = [0u8; 50];
+    reader.read_exact(&mut part).unwrap();
+    assert_eq!(part[..], plaintext[..50]);
+    reader.seek(SeekFrom::Start(header.len() as u64 + 400)).unwrap();
+    reader.read_exact(&mut part).unwrap();
+    assert_eq!(part[..], plaintext[400..450], "Reads after a seek should decrypt correctly");
+    reader.seek(SeekFrom::Current(-250)).unwrap();
+    reader.read_exact(&mut part).unwrap();
+    assert_eq!(part[..], plaintext[200..250]);
+    assert!(reader.seek(SeekFrom::Start(0)).is_err(), "The header lies before the keystream");
+}
+
+#[test]
+fn ysc2_io_reader_keeps_input_when_keystream_runs_out() {
+    use crate::io::DecryptingReader;
+    use crate::stream::KEYSTREAM_BLOCKS;
+    use std::io::{Cursor, Read};
+
+    let key = [0x1Cu8; 64].into();
+    let nonce = [0x1Du8; 64].into();
+    let mut cipher = Ysc2_512SpongeStreamCipher::new(&key, &nonce);
+    cipher.seek(KEYSTREAM_BLOCKS * 128 - 10);
+
+    // 키스트림이 모자라면 내부 reader에서 아무것도 읽지 않아야 합니다.
+    let mut reader = DecryptingReader::new(Cursor::new([0x5Au8; 20]), cipher);
+    let mut buf = [0u8; 20];
+    assert!(reader.read(&mut buf).is_err());
+    assert_eq!(reader.get_ref().position(), 0, "No input should be consumed");
+    assert_eq!(reader.read(&mut buf[..10]).unwrap(), 10);
+    assert_eq!(reader.get_ref().position(), 10);
+}
+
+#[test]
+fn ysc2_io_cipher_file_random_access() {
+    use crate::io::CipherFile;
+    use std::io::{Cursor, Read, Seek, SeekFrom, Write};
+
+    let key = [0x12u8; 64].into();
+    let nonce = [0x13u8; 64].into();
+    let mut plaintext = vec![0x5Cu8; 3000];
+    let mut file = CipherFile::new(Cursor::new(Vec::new()), Ysc2_512SpongeStreamCipher::new(&key, &nonce)).unwrap();
+    file.write_all(&plaintext).unwrap();
+
+    // 블록 경계를 가로지르는 위치를 덮어쓰고 다른 위치에서 읽습니다.
+    for (offset, len) in [(1000usize, 300usize), (127, 2), (2900, 100)] {
+        let patch: Vec<u8> = (0..len).map(|i| (offset + i) as u8).collect();
+        file.seek(SeekFrom::Start(offset as u64)).unwrap();
+        file.write_all(&patch).unwrap();
+        plaintext[offset..offset + len].copy_from_slice(&patch);
+    }
+    let mut middle = [0u8; 500];
+    file.seek(SeekFrom::Start(900)).unwrap();
+    file.read_exact(&mut middle).unwrap();
+    assert_eq!(middle[..], plaintext[900..1400]);
+
+    let (inner, _) = file.into_inner();
+    let mut decrypted = inner.into_inner();
+    Ysc2_512SpongeStreamCipher::new(&key, &nonce).apply_keystream(&mut decrypted);
+    assert_eq!(decrypted, plaintext, "The file should hold the one-shot encryption of the plaintext");
+
+    // 이미 데이터가 있는 위치에서 열면 그 오프셋부터 이어집니다.
+    let mut cursor = Cursor::new(vec![0u8; 10]);
+    cursor.seek(SeekFrom::End(0)).unwrap();
+    let mut file = CipherFile::new(cursor, Ysc2_512SpongeStreamCipher::new(&key, &nonce)).unwrap();
+    file.write_all(b"appended").unwrap();
+    let mut expected = [0u8; 18];
+    expected[10..].copy_from_slice(b"appended");
+    Ysc2_512SpongeStreamCipher::new(&key, &nonce).apply_keystream(&mut expected);
+    assert_eq!(file.into_inner().0.into_inner()[10..], expected[10..]);
+}
+
+#[cfg(feature = "auxcrypt")]
+#[test]
+fn ysc2_io_with_auxcrypt_stream() {
+    use crate::io::{DecryptingReader, EncryptingWriter};
+    use auxcrypt::AuxCrypt512Stream;
+    use std::io::{Read, Write};
+
+    let key = [0x14u8; 64].into();
+    let nonce = [0x15u8; 64].into();
+    let plaintext = b"AuxCrypt through the io adapters".repeat(20);
+
+    let mut writer = EncryptingWriter::new(Vec::new(), AuxCrypt512Stream::new(&key, &nonce));
+    writer.write_all(&plaintext).unwrap();
+    let (ciphertext, _) = writer.into_inner();
+    assert_ne!(ciphertext, plaintext);
+
+    let mut decrypted = Vec::new();
+    DecryptingReader::new(&ciphertext[..], AuxCrypt512Stream::new(&key, &nonce))
+        .read_to_end(&mut decrypted)
+        .unwrap();
+    assert_eq!(decrypted, plaintext);
+}
+
+#[test]
+fn ysc2_parallel_keystream_matches_serial() {
+    let key = [0x05u8; 64].into();
+    let nonce = [0x06u8; 64].into();
+
+    // 7 blocks: one 4-block parallel batch plus a 3-block tail.
+    let mut bulk = [0u8; 7 * 128];
+    Ysc2_512SpongeStreamCipher::new(&key, &nonce).apply_keystream(&mut bulk);
+
+    let mut serial = [0u8; 7 * 128];
+    let mut cipher = Ysc2_512SpongeStreamCipher::new(&key, &nonce);
+    for block in serial.chunks_mut(128) {
+        cipher.apply_keystream(block);
+    }
+    assert_eq!(bulk, serial, "Parallel and serial keystreams should match");
+}
+
+#[test]
+fn ysc2_stream_init_test_vectors() {
+    let hex = |bytes: &[u8]| bytes.iter().map(|b| format!("{b:02x}")).collect::<String>();
+    let nonce: [u8; 64] = core::array::from_fn(|i| 0x80 + i as u8);
+
+    let key: [u8; 64] = core::array::from_fn(|i| i as u8);
+    let mut keystream = [0u8; 32];
+    Ysc2_512SpongeStreamCipher::new(&key.into(), &nonce.into()).apply_keystream(&mut keystream);
+    assert_eq!(hex(&keystream), "25b5f7c2b799f5b99136f9c1903a6d5c0908e0c2e88a89c06235c06ff2c04214");
+
+    let key: [u8; 128] = core::array::from_fn(|i| i as u8);
+    let mut keystream = [0u8; 32];
+    Ysc2_1024SpongeStreamCipher::new(&key.into(), &nonce.into()).apply_keystream(&mut keystream);
+    assert_eq!(hex(&keystream), "ca19cce992a468113c3cc9279b669ba454d212f0d452b591dd2fecdde9839fac");
+}
+
+#[test]
+#[allow(deprecated)]
+fn ysc2_stream_cipher_aliases_keep_the_legacy_keystream() {
+    use crate::{Ysc2_1024LegacyStreamCipher, Ysc2_1024StreamCipher, Ysc2_512StreamCipher};
+
+    // 원래 이름의 별칭은 기존 데이터를 복호화할 수 있도록 레거시 키스트림을 유지합니다.
+    let nonce = [0x73u8; 64].into();
+    let key = [0x74u8; 64].into();
+    let (mut old, mut legacy) = ([0u8; 300], [0u8; 300]);
+    Ysc2_512StreamCipher::new(&key, &nonce).apply_keystream(&mut old);
+    Ysc2_512LegacyStreamCipher::new(&key, &nonce).apply_keystream(&mut legacy);
+    assert_eq!(old, legacy);
+
+    let key = [0x75u8; 128].into();
+    Ysc2_1024StreamCipher::new(&key, &nonce).apply_keystream(&mut old);
+    Ysc2_1024LegacyStreamCipher::new(&key, &nonce).apply_keystream(&mut legacy);
+    assert_eq!(old, legacy);
+}
+
+#[test]
+fn ysc2_stream_init_binds_variant_and_rounds() {
+    use crate::stream::{stream_iv, Ysc2LegacyStreamCore, Ysc2SpongeStreamCore};
+    use crate::variant::{Ysc2_1024, Ysc2_512, Ysc2_512R};
+
+    let iv = stream_iv::<Ysc2_512>();
+    assert_eq!(&iv[..3], &[u64::from_le_bytes(*b"YSC2-512"), u64::from_le_bytes(*b"-STREAM-"), u64::from_le_bytes(*b"V2\0\0\0\0\0\0")]);
+    assert_eq!(iv[6], 512 | (512 << 16) | (12 << 32) | (1 << 48) | (1 << 56));
+    assert_ne!(stream_iv::<Ysc2_512R<8>>(), iv, "Round count should be bound into the IV");
+
+    // 512비트 키 K와 1024비트 키 K || 0 은 레거시 초기화에서 같은 상태가 됩니다.
+    let key = [0x71u8; 64];
+    let mut long_key = [0u8; 128];
+    long_key[..64].copy_from_slice(&key);
+    let nonce = [0x72u8; 64].into();
+    assert_eq!(
+        Ysc2LegacyStreamCore::<Ysc2_512>::new(&key.into(), &nonce).state,
+        Ysc2LegacyStreamCore::<Ysc2_1024>::new(&long_key.into(), &nonce).state,
+    );
+    assert_ne!(
+        Ysc2SpongeStreamCore::<Ysc2_512>::new(&key.into(), &nonce).state,
+        Ysc2SpongeStreamCore::<Ysc2_1024>::new(&long_key.into(), &nonce).state,
+        "Sponge init should separate the variants"
+    );
+}
+
+#[test]
+#[should_panic(expected = "round count does not fit")]
+fn ysc2_stream_init_rejects_unencodable_rounds() {
+    crate::stream::stream_iv::<crate::variant::Ysc2_512R<{ 1 << 16 }>>();
+}
+
+#[test]
+fn ysc2_sponge_stream_blocks_known_plaintext_inversion() {
+    use crate::stream::{Ysc2LegacyStreamCore, Ysc2SpongeStreamCore};
+    use crate::variant::Ysc2_512;
+    use crate::Ysc2Permutation;
+
+    let key = [0x61u8; 64];
+    let nonce = [0x62u8; 64];
+    let to_words = |bytes: &[u8]| -> [u64; 16] {
+        core::array::from_fn(|i| u64::from_le_bytes(bytes[8 * i..8 * i + 8].try_into().unwrap()))
+    };
+    let keyed = Ysc2LegacyStreamCore::<Ysc2_512>::new(&key.into(), &nonce.into()).state;
+
+    // 레거시: 키스트림 블록 하나를 역순열하면 키 상태가 드러납니다.
+    let mut legacy = [0u8; 128];
+    Ysc2_512LegacyStreamCipher::new(&key.into(), &nonce.into()).apply_keystream(&mut legacy);
+    let mut recovered = to_words(&legacy);
+    Ysc2Permutation::<Ysc2_512>::permute_inverse(&mut recovered);
+    recovered[0] ^= 1;
+    assert_eq!(recovered, keyed, "Legacy keystream should be invertible (that is the flaw)");
+
+    // 스폰지 리비전: 블록은 rate(P(x)) || rate(P(P(x))) 이고 용량은 드러나지 않습니다.
+    let keyed = Ysc2SpongeStreamCore::<Ysc2_512>::new(&key.into(), &nonce.into()).state;
+    let mut block = [0u8; 128];
+    Ysc2_512SpongeStreamCipher::new(&key.into(), &nonce.into()).apply_keystream(&mut block);
+    let mut x = keyed;
+    for word in &mut x[..8] {
+        *word ^= 1;
+    }
+    let mut expected = [0u64; 16];
+    Ysc2Permutation::<Ysc2_512>::permute(&mut x);
+    expected[..8].copy_from_slice(&x[..8]);
+    Ysc2Permutation::<Ysc2_512>::permute(&mut x);
+    expected[8..].copy_from_slice(&x[..8]);
+    assert_eq!(to_words(&block), expected, "Sponge block should be rate(P(x)) || rate(P(P(x)))");
+    let mut inverted = to_words(&block);
+    Ysc2Permutation::<Ysc2_512>::permute_inverse(&mut inverted);
+    for word in &mut inverted[..8] {
+        *word ^= 1;
+    }
+    assert_ne!(inverted, keyed, "Sponge keystream should not invert to the keyed state");
+}
+
+#[test]
+fn ysc2_sponge_stream_pairs_invert_on_their_own() {
+    use crate::stream::Ysc2SpongeStreamCore;
+    use crate::variant::Ysc2_512;
+    use crate::Ysc2Permutation;
+
+    // 문서의 한계: 쌍 (i, i + 8)마다 용량 워드 하나만 맞히면 그 쌍의 키 상태가 드러납니다.
+    let key = [0x63u8; 64];
+    let nonce = [0x64u8; 64];
+    let keyed = Ysc2SpongeStreamCore::<Ysc2_512>::new(&key.into(), &nonce.into()).state;
+    let mut block = [0u8; 128];
+    Ysc2_512SpongeStreamCipher::new(&key.into(), &nonce.into()).apply_keystream(&mut block);
+    let block: [u64; 16] = core::array::from_fn(|i| u64::from_le_bytes(block[8 * i..8 * i + 8].try_into().unwrap()));
+    let mut x = keyed;
+    for word in &mut x[..8] {
+        *word ^= 1;
+    }
+    Ysc2Permutation::<Ysc2_512>::permute(&mut x);
+
+    for i in 0..8 {
+        // 다른 쌍은 아무 값이어도 결과에 영향을 주지 않습니다.
+        let mut guess = [0xA5A5_A5A5_A5A5_A5A5u64; 16];
+        guess[i] = block[i];
+        guess[i + 8] = x[i + 8];
+        let mut next = guess;
+        Ysc2Permutation::<Ysc2_512>::permute(&mut next);
+        assert_eq!(next[i], block[8 + i], "The right capacity guess for pair {i} should match the second half");
+
+        Ysc2Permutation::<Ysc2_512>::permute_inverse(&mut guess);
+        assert_eq!((guess[i] ^ 1, guess[i + 8]), (keyed[i], keyed[i + 8]), "Pair {i} should invert to the keyed words");
+    }
+}
+
+#[test]
+fn ysc2_keystream_statistical_bias() {
+    use cipher::{Iv, Key};
+    use rand_chacha::ChaCha8Rng;
+    use rand_core::{RngCore, SeedableRng};
+
+    const SAMPLES: usize = 512;
+    // 병렬 4블록과 직렬 1블록
+    const BLOCKS: usize = 5;
+
+    // 임의의 키/Nonce마다 처음 BLOCKS개 블록의 비트를 셉니다.
+    fn check<C: KeyIvInit + StreamCipher>(name: &str, rng: &mut ChaCha8Rng) {
+        let mut ones = [0u32; BLOCKS * 1024];
+        for _ in 0..SAMPLES {
+            let mut key = Key::<C>::default();
+            let mut nonce = Iv::<C>::default();
+            rng.fill_bytes(&mut key);
+            rng.fill_bytes(&mut nonce);
+            let mut keystream = [0u8; BLOCKS * 128];
+            C::new(&key, &nonce).apply_keystream(&mut keystream);
+
+            for (i, count) in ones.iter_mut().enumerate() {
+                *count += u32::from(keystream[i / 8] >> (i % 8) & 1);
+            }
+        }
+
+        // 비트마다 기댓값 256, 표준편차 약 11.3: 7시그마 이상 벗어나면 편향입니다.
+        for (i, &count) in ones.iter().enumerate() {
+            assert!(count.abs_diff(SAMPLES as u32 / 2) < 80, "{name}: bit {} of block {} is set {count} times out of {SAMPLES}", i % 1024, i / 1024);
+        }
+        // 워드마다 평균 1의 개수는 32에 가까워야 합니다 (표준편차 약 0.18).
+        for (w, word) in ones.chunks_exact(64).enumerate() {
+            let mean = word.iter().sum::<u32>() as f64 / SAMPLES as f64;
+            assert!((mean - 32.0).abs() < 1.5, "{name}: word {} of block {} averages {mean:.2} set bits", w % 16, w / 16);
+        }
+    }
+
+    let mut rng = ChaCha8Rng::seed_from_u64(0xB1A5);
+    check::<Ysc2_512SpongeStreamCipher>("Ysc2_512SpongeStreamCipher", &mut rng);
+    check::<Ysc2_1024SpongeStreamCipher>("Ysc2_1024SpongeStreamCipher", &mut rng);
+}
+
+#[test]
+fn ysc2_backends_parallel_blocks() {
+    use crate::backends::soft;
+    use crate::stream::{Ysc2LegacyStreamCore, Ysc2SpongeStreamCore};
+    use crate::variant::Ysc2_1024;
+    use auxcrypt::test_support::assert_par_blocks_match_serial as check;
+
+    let key = [0x0Du8; 128].into();
+    let nonce = [0x0Eu8; 64].into();
+    let mut par_core = Ysc2SpongeStreamCore::<Ysc2_1024>::new(&key, &nonce);
+    let mut serial_core = Ysc2SpongeStreamCore::<Ysc2_1024>::new(&key, &nonce);
+    check(&mut soft::Backend(&mut par_core), &mut soft::Backend(&mut serial_core));
+    assert_eq!(par_core.counter, 4, "Counter should advance by the number of blocks");
+    let mut legacy_par = Ysc2LegacyStreamCore::<Ysc2_1024>::new(&key, &nonce);
+    let mut legacy_serial = Ysc2LegacyStreamCore::<Ysc2_1024>::new(&key, &nonce);
+    check(&mut soft::Backend(&mut legacy_par), &mut soft::Backend(&mut legacy_serial));
+
+    #[cfg(all(feature = "ysc2_simd", target_arch = "x86_64"))]
+    if crate::Backend::Simd.is_available() {
+        use crate::backends::simd;
+        check(&mut simd::Backend(&mut par_core), &mut soft::Backend(&mut serial_core));
+        check(&mut simd::Backend(&mut legacy_par), &mut soft::Backend(&mut legacy_serial));
+    }
+}
+
+#[test]
+fn ysc2_backends_differential() {
+    use crate::backends::Backend;
+    use crate::stream::{Legacy, Sponge, StreamRevision, Ysc2KeystreamCore};
+    use crate::variant::{Ysc2Variant, Ysc2_1024, Ysc2_1024R, Ysc2_1024V2, Ysc2_512, Ysc2_512R, Ysc2_512V2};
+    use cipher::{Iv, Key, StreamCipherSeekCore};
+    use rand_chacha::ChaCha8Rng;
+    use rand_core::{RngCore, SeedableRng};
+
+    // 사용 가능한 모든 백엔드가 무작위 상태 / 키 / Nonce / 위치에서 Soft와
+    // 같아야 합니다: 순방향, 역방향, 직렬 및 병렬 키스트림.
+    fn check<V: Ysc2Variant, R: StreamRevision>(rng: &mut ChaCha8Rng) {
+        for _ in 0..16 {
+            let words: [u64; 16] = core::array::from_fn(|_| rng.next_u64());
+            let mut forward = words;
+            Backend::Soft.permute::<V>(&mut forward);
+            let mut inverse = words;
+            Backend::Soft.permute_inverse::<V>(&mut inverse);
+
+            let mut key = Key::<Ysc2KeystreamCore<V, R>>::default();
+            let mut nonce = Iv::<Ysc2KeystreamCore<V, R>>::default();
+            rng.fill_bytes(&mut key);
+            rng.fill_bytes(&mut nonce);
+            let pos = u128::from(rng.next_u64() >> 1);
+            // 7블록: 4블록 병렬 묶음 하나와 3블록 꼬리
+            let keystream = |backend: Backend| {
+                let mut out = vec![0u8; 7 * 128];
+                let mut core = Ysc2KeystreamCore::<V, R>::new(&key, &nonce);
+                core.set_block_pos(pos.try_into().unwrap());
+                core.process_with(backend, KeystreamInto(&mut out));
+                assert_eq!(u128::from(core.get_block_pos()), pos + 7);
+                out
+            };
+            let expected = keystream(Backend::Soft);
+
+            for backend in Backend::ALL.into_iter().filter(|backend| backend.is_available()) {
+                let mut state = words;
+                backend.permute::<V>(&mut state);
+                assert_eq!(state, forward, "{backend:?} permutation must match Soft");
+                let mut state = words;
+                backend.permute_inverse::<V>(&mut state);
+                assert_eq!(state, inverse, "{backend:?} inverse must match Soft");
+                assert_eq!(keystream(backend), expected, "{backend:?} keystream must match Soft");
+                // 트리 해시 모드가 쓰는 4상태 순열
+                #[cfg(feature = "ysc2x")]
+                {
+                    let mut states = [words, forward, inverse, words.map(|word| !word)];
+                    let mut serial = states;
+                    backend.permute_x4::<V>(&mut states);
+                    serial.iter_mut().for_each(|state| Backend::Soft.permute::<V>(state));
+                    assert_eq!(states, serial, "{backend:?} four-state permutation must match Soft");
+                }
+            }
+        }
+    }
+
+    let mut rng = ChaCha8Rng::seed_from_u64(0x7532);
+    check::<Ysc2_512, Legacy>(&mut rng);
+    check::<Ysc2_512, Sponge>(&mut rng);
+    check::<Ysc2_1024, Sponge>(&mut rng);
+    check::<Ysc2_512V2, Sponge>(&mut rng);
+    check::<Ysc2_1024V2, Legacy>(&mut rng);
+    check::<Ysc2_512R<5>, Sponge>(&mut rng);
+    check::<Ysc2_1024R<20>, Sponge>(&mut rng);
+}
+
+#[test]
+fn ysc2_backend_selection() {
+    use crate::Backend;
+
+    assert!(Backend::Soft.is_available());
+    assert!(Backend::active().is_available());
+    if !Backend::Simd.is_available() {
+        assert!(Backend::Simd.select().is_err(), "Unavailable backend must be rejected");
+    }
+}
+
+#[test]
+#[cfg(all(feature = "ysc2_simd", target_arch = "x86_64"))]
+fn ysc2_simd_vs_soft_permutation() {
+    use crate::backends::{simd, soft};
+    use crate::variant::{Ysc2_1024, Ysc2_512};
+
+    if !crate::Backend::Simd.is_available() {
+        return;
+    }
+
+    let mut state = [0u64; 16];
+    for (i, word) in state.iter_mut().enumerate() {
+        *word = (i as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    }
+
+    let mut soft_state = state;
+    let mut simd_state = state;
+    soft::permutation::<Ysc2_512>(&mut soft_state);
+    simd::permutation::<Ysc2_512>(&mut simd_state);
+    assert_eq!(soft_state, simd_state, "SIMD and Soft backends must compute the same permutation");
+
+    soft::permutation::<Ysc2_1024>(&mut soft_state);
+    simd::permutation::<Ysc2_1024>(&mut simd_state);
+    assert_eq!(soft_state, simd_state, "SIMD and Soft backends must compute the same permutation");
+}
+
+//======================================================================
+// Block Cipher (Even-Mansour) Tests
+//======================================================================
+
+#[test]
+fn ysc2_512_block_cipher_roundtrip() {
+    use crate::Ysc2_512BlockCipher;
+    use cipher::{BlockDecrypt, BlockEncrypt, KeyInit};
+
+    let cipher = Ysc2_512BlockCipher::new(&[0x07u8; 64].into());
+    let mut blocks = [[0u8; 128].into(), [1u8; 128].into()];
+    let original = blocks;
+
+    cipher.encrypt_blocks(&mut blocks);
+    assert_ne!(blocks[0], original[0], "Ciphertext should not match plaintext");
+    assert_ne!(blocks[0], blocks[1], "Distinct blocks should encrypt differently");
+
+    cipher.decrypt_blocks(&mut blocks);
+    assert_eq!(blocks, original, "Decrypted blocks should match original");
+
+    let other = Ysc2_512BlockCipher::new(&[0x08u8; 64].into());
+    let mut block = original[0];
+    let mut other_block = original[0];
+    cipher.encrypt_block(&mut block);
+    other.encrypt_block(&mut other_block);
+    assert_ne!(block, other_block, "Different keys should give different permutations");
+}
+
+#[test]
+fn ysc2_1024_block_cipher_is_even_mansour() {
+    use crate::variant::{Ysc2Variant, Ysc2_1024};
+    use crate::{Ysc2State, Ysc2_1024BlockCipher};
+    use cipher::{BlockEncrypt, KeyInit};
+
+    let key = [0x5Au8; 128];
+    let plaintext = [0xC3u8; 128];
+
+    // Ki = P(key ^ (D || i)), E(x) = P(x ^ K1) ^ K2
+    let domain = Ysc2_1024::BLOCK_CIPHER_DOMAIN.as_bytes();
+    let derive = |index: u8| {
+        let mut k = Ysc2State::from_bytes(&key);
+        k.xor_in(127 - domain.len(), domain);
+        k.xor_in(127, &[index]);
+        k.permute::<Ysc2_1024>();
+        k.to_bytes()
+    };
+    let k1 = derive(1);
+    let k2 = derive(2);
+
+    let mut expected = Ysc2State::from_bytes(&plaintext);
+    expected.xor_in(0, &k1);
+    expected.permute::<Ysc2_1024>();
+    expected.xor_in(0, &k2);
+
+    let mut block = plaintext.into();
+    Ysc2_1024BlockCipher::new(&key.into()).encrypt_block(&mut block);
+    assert_eq!(block[..], expected.to_bytes()[..], "Block cipher should match the Even-Mansour definition");
+}
+
+//======================================================================
+// Raw Permutation Tests
+//======================================================================
+
+#[test]
+fn ysc2_permutation_word_and_byte_forms_agree() {
+    use crate::{Ysc2Permutation, Ysc2State};
+    use crate::variant::Ysc2_512;
+
+    let mut bytes = [0u8; 128];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = i as u8;
+    }
+
+    let mut state = Ysc2State::from_bytes(&bytes);
+    let mut words = *state.words();
+    Ysc2Permutation::<Ysc2_512>::permute(&mut words);
+    Ysc2Permutation::<Ysc2_512>::permute_bytes(&mut bytes);
+    state.permute::<Ysc2_512>();
+
+    assert_eq!(state.words(), &words, "Word and state forms should agree");
+    assert_eq!(state.to_bytes(), bytes, "Byte and state forms should agree");
+}
+
+#[test]
+fn ysc2_inverse_permutation_roundtrip() {
+    use crate::backends::soft;
+    use crate::variant::{Ysc2_1024, Ysc2_512};
+    use crate::{Ysc2Permutation, Ysc2State};
+
+    let mut words = [0u64; 16];
+    for (i, word) in words.iter_mut().enumerate() {
+        *word = (i as u64 + 1).wrapping_mul(0xA076_1D64_78BD_642F);
+    }
+
+    let mut state = words;
+    soft::permutation::<Ysc2_512>(&mut state);
+    assert_ne!(state, words, "Permutation should change the state");
+    soft::permutation_inverse::<Ysc2_512>(&mut state);
+    assert_eq!(state, words, "Soft inverse should undo the permutation");
+
+    #[cfg(all(feature = "ysc2_simd", target_arch = "x86_64"))]
+    if crate::Backend::Simd.is_available() {
+        use crate::backends::simd;
+
+        simd::permutation::<Ysc2_1024>(&mut state);
+        let mut soft_inverse = state;
+        soft::permutation_inverse::<Ysc2_1024>(&mut soft_inverse);
+        simd::permutation_inverse::<Ysc2_1024>(&mut state);
+        assert_eq!(state, soft_inverse, "SIMD and Soft inverses must agree");
+        assert_eq!(state, words, "SIMD inverse should undo the permutation");
+    }
+
+    let mut bytes = Ysc2State::from_words(words).to_bytes();
+    Ysc2Permutation::<Ysc2_1024>::permute_bytes(&mut bytes);
+    Ysc2Permutation::<Ysc2_1024>::permute_inverse_bytes(&mut bytes);
+    assert_eq!(bytes, Ysc2State::from_words(words).to_bytes(), "Byte-oriented inverse should round-trip");
+}
+
+#[test]
+fn ysc2_custom_round_variants() {
+    use crate::backends::soft;
+    use crate::consts::{round_constant, RC};
+    use crate::generic::{self, PlainWords};
+    use crate::stream::Ysc2SpongeStreamCore;
+    use crate::variant::{Revision, Ysc2_1024R, Ysc2_512R};
+    use crate::Ysc2Permutation;
+
+    assert!(RC.iter().enumerate().all(|(i, &rc)| rc == round_constant(i)), "Generator should extend RC");
+
+    let key = [0x51u8; 64];
+    let nonce = [0x52u8; 64];
+    let mut expected = [0u8; 300];
+    let mut actual = [0u8; 300];
+    Ysc2_512SpongeStreamCipher::new(&key.into(), &nonce.into()).apply_keystream(&mut expected);
+    cipher::StreamCipherCoreWrapper::<Ysc2SpongeStreamCore<Ysc2_512R<12>>>::new(&key.into(), &nonce.into())
+        .apply_keystream(&mut actual);
+    assert_eq!(actual, expected, "Ysc2_512R<12> should be identical to Ysc2_512");
+
+    let mut words = [0u64; 16];
+    for (i, word) in words.iter_mut().enumerate() {
+        *word = (i as u64 + 5).wrapping_mul(0xE703_7ED1_A0B4_28DB);
+    }
+
+    // RC 표보다 많은 라운드도 동작해야 합니다.
+    let mut long = words;
+    Ysc2Permutation::<Ysc2_1024R<40>>::permute(&mut long);
+    let mut reference = words;
+    generic::permutation_rounds(&mut PlainWords, &mut reference, Revision::V1, 40);
+    assert_eq!(long, reference, "40-round permutation should match the generic evaluator");
+    Ysc2Permutation::<Ysc2_1024R<40>>::permute_inverse(&mut long);
+    assert_eq!(long, words, "40-round inverse should round-trip");
+
+    let mut reduced = words;
+    soft::permutation::<Ysc2_512R<5>>(&mut reduced);
+    let mut reference = words;
+    generic::permutation_rounds(&mut PlainWords, &mut reference, Revision::V1, 5);
+    assert_eq!(reduced, reference, "5-round permutation should match the generic evaluator");
+}
+
+#[test]
+fn ysc2_revision_1_outputs_are_unchanged() {
+    use crate::variant::Ysc2_512;
+    use crate::Ysc2Permutation;
+
+    let mut words: [u64; 16] = core::array::from_fn(|i| (i as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    Ysc2Permutation::<Ysc2_512>::permute(&mut words);
+    assert_eq!((words[0], words[15]), (0x281e_8b13_d37a_0ffe, 0xae6d_d184_bae4_f22d));
+}
+
+#[test]
+fn ysc2_revision_2_lfsr_constants() {
+    use crate::backends::soft;
+    use crate::consts::{
+        lfsr_next, lfsr_prev, lfsr_round_constants, LfsrRoundConstants, LFSR_RC, LFSR_RC_ROUNDS, LFSR_SEED,
+    };
+    use crate::generic::{self, PlainWords};
+    use crate::variant::{Rev2, Revision, Ysc2_512, Ysc2_512R, Ysc2_512V2};
+    use crate::Ysc2Permutation;
+
+    assert_eq!(LFSR_RC[0][0], lfsr_next(LFSR_SEED));
+    let mut x = LFSR_RC[LFSR_RC_ROUNDS - 1][15];
+    for i in 0..16 {
+        x = lfsr_next(x);
+        assert_eq!(lfsr_round_constants(LFSR_RC_ROUNDS)[i], x, "Generator should continue past the table");
+    }
+    assert_eq!(lfsr_prev(lfsr_next(LFSR_SEED)), LFSR_SEED);
+
+    // 커서는 앞뒤 어느 방향으로 걸어도 임의 접근과 같은 상수를 냅니다.
+    let mut lfsr = LfsrRoundConstants::new();
+    for round in (0..2 * LFSR_RC_ROUNDS).chain((0..2 * LFSR_RC_ROUNDS).rev()) {
+        assert_eq!(lfsr.constants(round), lfsr_round_constants(round), "Cursor should match round {round}");
+    }
+
+    let words: [u64; 16] = core::array::from_fn(|i| (i as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    let mut v1 = words;
+    let mut v2 = words;
+    Ysc2Permutation::<Ysc2_512>::permute(&mut v1);
+    Ysc2Permutation::<Ysc2_512V2>::permute(&mut v2);
+    assert_ne!(v1, v2, "Revisions should be different permutations");
+
+    let mut reference = words;
+    generic::permutation_rounds(&mut PlainWords, &mut reference, Revision::V2, 12);
+    let mut soft_v2 = words;
+    soft::permutation::<Ysc2_512V2>(&mut soft_v2);
+    assert_eq!(soft_v2, reference, "Soft backend should match the generic evaluator");
+    assert_eq!(v2, reference, "Active backend should match the generic evaluator");
+
+    // 상수표를 넘는 라운드 수와 역순열
+    let mut long = words;
+    Ysc2Permutation::<Rev2<Ysc2_512R<40>>>::permute(&mut long);
+    let mut reference = words;
+    generic::permutation_rounds(&mut PlainWords, &mut reference, Revision::V2, 40);
+    assert_eq!(long, reference, "40-round revision 2 should match the generic evaluator");
+    Ysc2Permutation::<Rev2<Ysc2_512R<40>>>::permute_inverse(&mut long);
+    assert_eq!(long, words, "Revision 2 inverse should round-trip");
+
+    #[cfg(all(feature = "ysc2_simd", target_arch = "x86_64"))]
+    if crate::Backend::Simd.is_available() {
+        use crate::backends::simd;
+        use crate::variant::Ysc2_1024V2;
+
+        let mut state = words;
+        simd::permutation::<Ysc2_1024V2>(&mut state);
+        let mut expected = words;
+        soft::permutation::<Ysc2_1024V2>(&mut expected);
+        assert_eq!(state, expected, "SIMD and Soft revision 2 must agree");
+        simd::permutation_inverse::<Ysc2_1024V2>(&mut state);
+        assert_eq!(state, words, "SIMD revision 2 inverse should round-trip");
+
+        let mut states: [[u64; 16]; 4] = core::array::from_fn(|b| core::array::from_fn(|i| words[i] ^ b as u64));
+        let mut serial = states;
+        simd::permutation_x4::<Ysc2_1024V2>(&mut states);
+        for state in serial.iter_mut() {
+            soft::permutation::<Ysc2_1024V2>(state);
+        }
+        assert_eq!(states, serial, "Four-way SIMD revision 2 must agree with Soft");
+    }
+}
+
+#[test]
+fn ysc2_state_xor_in_and_extract() {
+    use crate::Ysc2State;
+
+    let mut state = Ysc2State::new();
+    state.xor_in(3, b"duplex");
+    state.xor_in(3, b"du");
+
+    let mut out = [0u8; 8];
+    state.extract(2, &mut out);
+    assert_eq!(&out, b"\0\0\0plex\0", "XOR-in should be undone by a second XOR");
+    assert_eq!(state.words()[0], u64::from_le_bytes(*b"\0\0\0\0\0ple"));
+}
+
+//======================================================================
+// Generic Evaluator Tests
+//======================================================================
+
+#[test]
+fn ysc2_generic_permutation_matches_soft() {
+    use crate::backends::soft;
+    use crate::generic::{self, Bitwise, PlainBits, PlainWords};
+    use crate::variant::Ysc2_512;
+
+    let mut words = [0u64; 16];
+    for (i, word) in words.iter_mut().enumerate() {
+        *word = (i as u64 + 7).wrapping_mul(0xD6E8_FEB8_6659_FD93);
+    }
+    let mut expected = words;
+    soft::permutation::<Ysc2_512>(&mut expected);
+
+    let mut plain = words;
+    generic::permutation::<Ysc2_512, _>(&mut PlainWords, &mut plain);
+    assert_eq!(plain, expected, "Word evaluator should match the soft backend");
+
+    let mut ops = Bitwise(PlainBits);
+    let mut bits: [[bool; 64]; 16] = core::array::from_fn(|i| core::array::from_fn(|k| (words[i] >> k) & 1 == 1));
+    generic::permutation::<Ysc2_512, _>(&mut ops, &mut bits);
+    let from_bits = bits.map(|word| word.iter().rev().fold(0u64, |acc, &bit| (acc << 1) | bit as u64));
+    assert_eq!(from_bits, expected, "Bit evaluator should match the soft backend");
+}
+
+#[test]
+fn ysc2_generic_keystream_matches_stream_cipher() {
+    use crate::generic::{self, PlainWords};
+    use crate::variant::Ysc2_512;
+
+    let key = [0x41u8; 64];
+    let nonce = [0x42u8; 64];
+    let mut keystream = [0u8; 3 * 128];
+    let mut legacy_keystream = [0u8; 3 * 128];
+    Ysc2_512SpongeStreamCipher::new(&key.into(), &nonce.into()).apply_keystream(&mut keystream);
+    Ysc2_512LegacyStreamCipher::new(&key.into(), &nonce.into()).apply_keystream(&mut legacy_keystream);
+
+    let to_words = |bytes: &[u8]| -> Vec<u64> {
+        bytes.chunks_exact(8).map(|c| u64::from_le_bytes(c.try_into().unwrap())).collect()
+    };
+    let state = generic::stream_init_sponge::<Ysc2_512, _>(&mut PlainWords, &to_words(&key), &to_words(&nonce));
+    for (counter, expected) in (1u64..).zip(keystream.chunks(128)) {
+        let block = generic::keystream_block_sponge::<Ysc2_512, _>(&mut PlainWords, &state, &counter);
+        assert_eq!(block[..], to_words(expected)[..], "Keystream block {counter} should match");
+    }
+    let state = generic::stream_init::<Ysc2_512, _>(&mut PlainWords, &to_words(&key), &to_words(&nonce));
+    for (counter, expected) in (1u64..).zip(legacy_keystream.chunks(128)) {
+        let block = generic::keystream_block::<Ysc2_512, _>(&mut PlainWords, &state, &counter);
+        assert_eq!(block[..], to_words(expected)[..], "Legacy keystream block {counter} should match");
+    }
+}
+
+//======================================================================
+// Circuit Export Tests
+//======================================================================
+
+#[cfg(feature = "circuit")]
+fn bytes_to_bits(bytes: &[u8]) -> Vec<bool> {
+    bytes.iter().flat_map(|byte| (0..8).map(move |k| (byte >> k) & 1 == 1)).collect()
+}
+
+#[cfg(feature = "circuit")]
+fn bits_to_bytes(bits: &[bool]) -> Vec<u8> {
+    bits.chunks(8)
+        .map(|byte| byte.iter().enumerate().fold(0u8, |acc, (k, &bit)| acc | ((bit as u8) << k)))
+        .collect()
+}
+
+#[cfg(feature = "circuit")]
+#[test]
+fn ysc2_permutation_circuit_matches_permutation() {
+    use crate::circuit::permutation_circuit;
+    use crate::variant::{Ysc2Variant, Ysc2_512};
+    use crate::Ysc2Permutation;
+
+    let circuit = permutation_circuit::<Ysc2_512>();
+    assert_eq!(circuit.input_sizes(), &[1024]);
+    assert_eq!(circuit.output_sizes(), &[1024]);
+    assert_eq!(circuit.and_count(), 512 * Ysc2_512::ROUNDS, "g(x) costs 64 AND gates per word");
+
+    let mut bytes = [0u8; 128];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = (i as u8).wrapping_mul(37).wrapping_add(11);
+    }
+    let output = circuit.evaluate(&bytes_to_bits(&bytes));
+    Ysc2Permutation::<Ysc2_512>::permute_bytes(&mut bytes);
+    assert_eq!(bits_to_bytes(&output), bytes, "Circuit should compute the permutation");
+
+    let bristol = circuit.to_bristol();
+    let mut lines = bristol.lines();
+    assert_eq!(lines.next(), Some(format!("{} {}", circuit.gates().len(), circuit.num_wires()).as_str()));
+    assert_eq!(lines.next(), Some("1 1024"));
+    assert_eq!(lines.next(), Some("1 1024"));
+    assert_eq!(bristol.matches(" AND").count(), circuit.and_count());
+    assert!(circuit.to_json().starts_with("{\"inputs\":[1024],\"outputs\":[1024],"));
+}
+
+#[cfg(feature = "circuit")]
+#[test]
+fn ysc2_keystream_circuit_matches_stream_cipher() {
+    use crate::circuit::{keystream_circuit, legacy_keystream_circuit};
+    use crate::variant::Ysc2_1024;
+
+    let key = [0x31u8; 128];
+    let nonce = [0x32u8; 64];
+    let mut keystream = [0u8; 256];
+    Ysc2_1024SpongeStreamCipher::new(&key.into(), &nonce.into()).apply_keystream(&mut keystream);
+
+    let circuit = keystream_circuit::<Ysc2_1024>();
+    assert_eq!(circuit.input_sizes(), &[1024, 512, 64]);
+    for (counter, expected) in [1u64, 2].into_iter().zip(keystream.chunks(128)) {
+        let inputs = [&key[..], &nonce[..], &counter.to_le_bytes()[..]].concat();
+        let output = circuit.evaluate(&bytes_to_bits(&inputs));
+        assert_eq!(bits_to_bytes(&output), expected, "Circuit should compute keystream block {counter}");
+    }
+
+    let mut legacy = [0u8; 128];
+    crate::Ysc2_1024LegacyStreamCipher::new(&key.into(), &nonce.into()).apply_keystream(&mut legacy);
+    let inputs = [&key[..], &nonce[..], &1u64.to_le_bytes()[..]].concat();
+    let output = legacy_keystream_circuit::<Ysc2_1024>().evaluate(&bytes_to_bits(&inputs));
+    assert_eq!(bits_to_bytes(&output), legacy, "Legacy circuit should compute the legacy keystream");
+}
+
+#[cfg(feature = "circuit")]
+#[test]
+fn ysc2_permutation_cost_matches_circuit() {
+    use crate::circuit::permutation_circuit;
+    use crate::cost::permutation_cost;
+    use crate::variant::{Revision, Ysc2Variant, Ysc2_512};
+
+    let circuit = permutation_circuit::<Ysc2_512>();
+    let cost = permutation_cost(Revision::V1, Ysc2_512::ROUNDS);
+    assert_eq!(cost.and_gates, circuit.and_count() as u64);
+    assert_eq!(cost.xor_gates, circuit.xor_count() as u64);
+    assert_eq!(cost.not_gates, circuit.inv_count() as u64);
+    assert_eq!(cost.and_depth, Ysc2_512::ROUNDS as u32, "g(x) has AND depth 1 per round");
+    assert_eq!(permutation_cost(Revision::V1, 4).and_depth, 4);
+}
+
+#[cfg(feature = "circuit")]
+#[test]
+fn ysc2_construction_costs() {
+    use crate::cost::{aead_cost, hash_cost, keystream_cost, permutation_cost};
+    use crate::variant::{Revision, Ysc2_512};
+
+    let perm = permutation_cost(Revision::V1, 8);
+    let keystream = keystream_cost::<Ysc2_512>(8, 3);
+    assert_eq!(keystream.and_depth, 32, "Key and nonce absorption and the two halves of a block are sequential");
+    assert_eq!(keystream.and_gates, 8 * perm.and_gates, "The key fills the left half, so no AND is free");
+
+    // 메시지 2블록 + 패딩 1블록 흡수, 1블록 짜내기
+    assert_eq!(hash_cost(Revision::V1, 8, 128, 64).and_depth, 4 * 8);
+    // 도메인/Nonce/AD 3번, 청크 2개 x 2번, 태그 1번
+    assert_eq!(aead_cost::<Ysc2_512>(8, 100).and_depth, 8 * 8);
+}
+
+#[cfg(all(feature = "circuit", feature = "auxcrypt"))]
+#[test]
+fn ysc2_auxcrypt_cost_model_matches_auxcrypt() {
+    use crate::cost::{auxcrypt_permutation_cost, auxcrypt_permutation_rounds};
+    use crate::generic::PlainWords;
+    use auxcrypt::variant::{AuxCrypt512, AuxCrypt512V3, AuxCryptVariant, Revision};
+    use auxcrypt::AuxCryptPermutation;
+
+    let mut words = [0u64; 16];
+    for (i, word) in words.iter_mut().enumerate() {
+        *word = (i as u64 + 3).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    }
+    let mut expected = words;
+    AuxCryptPermutation::<AuxCrypt512>::permute(&mut expected);
+    auxcrypt_permutation_rounds(&mut PlainWords, &mut words, Revision::V1, AuxCrypt512::ROUNDS);
+    assert_eq!(words, expected, "Cost model should evaluate the AuxCrypt permutation");
+
+    let cost = auxcrypt_permutation_cost(Revision::V1, AuxCrypt512::ROUNDS);
+    assert_eq!((cost.and_depth, cost.and_gates), (0, 0), "AuxCrypt's f is affine");
+
+    let mut words = expected;
+    let mut expected_v3 = words;
+    AuxCryptPermutation::<AuxCrypt512V3>::permute(&mut expected_v3);
+    auxcrypt_permutation_rounds(&mut PlainWords, &mut words, Revision::V3, AuxCrypt512V3::ROUNDS);
+    assert_eq!(words, expected_v3, "Cost model should evaluate revision 3");
+    let cost = auxcrypt_permutation_cost(Revision::V3, AuxCrypt512V3::ROUNDS);
+    assert!(cost.and_depth > 0 && cost.and_gates > 0, "Revision 3's f is non-linear");
+}
+
+#[cfg(all(feature = "circuit", feature = "auxcrypt"))]
+#[test]
+#[should_panic(expected = "AuxCrypt has round constants for at most 20 rounds")]
+fn ysc2_auxcrypt_cost_model_rejects_too_many_rounds() {
+    crate::cost::auxcrypt_permutation_cost(auxcrypt::variant::Revision::V2, 21);
+}
+
 //======================================================================
 // Sponge (YSC2-X) Tests
 //======================================================================
@@ -104,6 +1150,397 @@ fn ysc2_512_hash_and_xof() {
     
     assert_eq!(&out1[..], &fixed_out[..32], "Fixed hash should match the first part of XOF output");
 }
+#[cfg(feature = "ysc2x")]
+#[test]
+fn ysc2_sp800_185_encodings() {
+    use crate::custom::{bytepad, encode_string, left_encode, right_encode};
+
+    assert_eq!(left_encode(0), [1, 0]);
+    assert_eq!(left_encode(255), [1, 255]);
+    assert_eq!(left_encode(256), [2, 1, 0]);
+    assert_eq!(left_encode(u64::MAX), [8, 255, 255, 255, 255, 255, 255, 255, 255]);
+    assert_eq!(right_encode(0), [0, 1]);
+    assert_eq!(right_encode(256), [1, 0, 2]);
+    assert_eq!(encode_string(b""), [1, 0]);
+    assert_eq!(encode_string(b"Email Signature")[..2], [1, 120]);
+
+    let padded = bytepad(b"abc", 64);
+    assert_eq!(padded.len(), 64);
+    assert_eq!(padded[..5], [1, 64, b'a', b'b', b'c']);
+    assert!(padded[5..].iter().all(|&b| b == 0));
+    assert_eq!(bytepad(&[0xFF; 62], 64).len(), 64);
+    assert_eq!(bytepad(&[0xFF; 63], 64).len(), 128);
+}
+
+#[cfg(feature = "ysc2x")]
+#[test]
+fn ysc2_custom_hasher_separates_instances() {
+    use crate::custom::{bytepad, encode_string, Ysc2CustomHasher};
+    use crate::variant::Ysc2_512;
+    use crate::Ysc2_512CustomHasher;
+
+    fn custom(name: &[u8], customization: &[u8], message: &[u8]) -> [u8; 64] {
+        let mut hasher = Ysc2_512CustomHasher::new(name, customization);
+        hasher.update(message);
+        let mut out = [0u8; 64];
+        hasher.finalize_xof().read(&mut out);
+        out
+    }
+    fn plain(message: &[u8]) -> [u8; 64] {
+        let mut hasher = Ysc2_512Hasher::default();
+        hasher.update(message);
+        let mut out = [0u8; 64];
+        hasher.finalize_xof().read(&mut out);
+        out
+    }
+
+    // cSHAKE처럼 이름과 사용자 정의 문자열이 모두 비면 일반 해시와 같습니다.
+    assert_eq!(custom(b"", b"", b"message"), plain(b"message"));
+
+    let tagged = custom(b"", b"Email Signature", b"message");
+    assert_ne!(tagged, plain(b"message"));
+    assert_ne!(tagged, custom(b"Email Signature", b"", b"message"), "Name and customization must not be swappable");
+    assert_ne!(custom(b"ab", b"c", b"message"), custom(b"a", b"bc", b"message"), "Field boundaries must be encoded");
+
+    // 접두어를 직접 붙인 일반 해시와도 구분됩니다 (용량 영역의 도메인).
+    let prefix = bytepad(&[encode_string(b""), encode_string(b"Email Signature")].concat(), 64);
+    assert_ne!(tagged, plain(&[prefix, b"message".to_vec()].concat()));
+
+    // 여러 번에 나눠 넣어도 같은 결과입니다.
+    let mut hasher = Ysc2CustomHasher::<Ysc2_512>::new(b"", b"Email Signature");
+    hasher.update(b"mes");
+    hasher.update(b"sage");
+    let mut out = [0u8; 64];
+    hasher.finalize_xof().read(&mut out);
+    assert_eq!(out, tagged);
+
+    let hex: String = tagged[..16].iter().map(|b| format!("{b:02x}")).collect();
+    assert_eq!(hex, "003daf6539f64426954def47b9b1574e");
+}
+
+#[cfg(feature = "ysc2x")]
+#[test]
+fn ysc2_kmac_matches_construction() {
+    use crate::custom::{bytepad, encode_string, right_encode, Ysc2CustomHasher};
+    use crate::kmac::Ysc2Kmac;
+    use crate::variant::Ysc2_512;
+    use crate::{Ysc2_512Kmac, Ysc2_512KmacXof};
+    use digest::{consts::U32, FixedOutput};
+
+    let key = b"a key of twenty-six bytes!";
+    let message = b"message to authenticate";
+    let custom = b"My Tagged Application";
+
+    let expected = |length_bits: u64, out: &mut [u8]| {
+        let mut hasher = Ysc2CustomHasher::<Ysc2_512>::new(b"KMAC", custom);
+        hasher.update(&bytepad(&encode_string(key), 64));
+        hasher.update(message);
+        hasher.update(&right_encode(length_bits));
+        hasher.finalize_xof().read(out);
+    };
+
+    let mut mac = Ysc2_512Kmac::new_customized(key, custom).unwrap();
+    Update::update(&mut mac, message);
+    let tag = mac.finalize_fixed();
+    let mut reference = [0u8; 64];
+    expected(512, &mut reference);
+    assert_eq!(tag[..], reference);
+
+    // 요청한 출력 길이가 계산에 묶이므로 짧은 태그는 긴 태그의 앞부분이 아닙니다.
+    let mut mac = Ysc2Kmac::<Ysc2_512, U32>::new_customized(key, custom).unwrap();
+    Update::update(&mut mac, message);
+    let short = mac.finalize_fixed();
+    expected(256, &mut reference[..32]);
+    assert_eq!(short[..], reference[..32]);
+    assert_ne!(short[..], tag[..32]);
+
+    let mut mac = Ysc2_512Kmac::new_customized(key, custom).unwrap();
+    Update::update(&mut mac, message);
+    let mut odd = [0u8; 21];
+    mac.finalize_variable(&mut odd);
+    expected(168, &mut reference[..21]);
+    assert_eq!(odd, reference[..21]);
+
+    // XOF 변형은 길이로 0을 인코딩하고, 어떤 길이로도 읽을 수 있습니다.
+    let mut xof = Ysc2_512KmacXof::new_customized(key, custom).unwrap();
+    xof.update(message);
+    let mut stream = [0u8; 100];
+    xof.finalize_xof().read(&mut stream);
+    let mut reference = [0u8; 100];
+    expected(0, &mut reference);
+    assert_eq!(stream, reference);
+    assert_ne!(stream[..64], tag[..]);
+
+    let hex: String = tag[..16].iter().map(|b| format!("{b:02x}")).collect();
+    assert_eq!(hex, "a07930ab47096061c0cbb954f7d63e71");
+}
+
+#[cfg(feature = "ysc2x")]
+#[test]
+fn ysc2_kmac_keys_and_verification() {
+    use crate::Ysc2_1024Kmac;
+    use digest::Mac;
+
+    // 어떤 길이의 키든 받지만, MIN_KEY_BYTES보다 짧으면 거부합니다.
+    assert!(<Ysc2_1024Kmac as Mac>::new_from_slice(&[7u8; 15]).is_err());
+    for len in [16, 33, 128, 300] {
+        assert!(<Ysc2_1024Kmac as Mac>::new_from_slice(&vec![7u8; len]).is_ok());
+    }
+
+    let tag_with = |key: &[u8], message: &[u8]| {
+        let mut mac = <Ysc2_1024Kmac as Mac>::new_from_slice(key).unwrap();
+        Update::update(&mut mac, message);
+        mac.finalize().into_bytes()
+    };
+    let key = [0x42u8; 128];
+    let tag = tag_with(&key, b"message");
+    assert_ne!(tag, tag_with(&key[..127], b"message"), "Key length must be bound");
+    assert_ne!(tag, tag_with(&[&key[..], &[0]].concat(), b"message"), "Key padding must be injective");
+
+    // KeyInit::new는 빈 사용자 정의 문자열과 같습니다.
+    let mut mac = <Ysc2_1024Kmac as Mac>::new(&key.into());
+    Update::update(&mut mac, b"message");
+    mac.clone().verify_slice(&tag).expect("correct tag should verify");
+    let mut wrong = tag;
+    wrong[0] ^= 1;
+    assert!(mac.verify_slice(&wrong).is_err());
+
+    let mut customized = Ysc2_1024Kmac::new_customized(&key, b"other").unwrap();
+    Update::update(&mut customized, b"message");
+    assert!(customized.verify_slice(&tag).is_err(), "Customization must change the tag");
+}
+
+#[cfg(feature = "ysc2x")]
+#[test]
+fn ysc2_tuple_hash_encodes_items() {
+    use crate::custom::{encode_string, right_encode, Ysc2CustomHasher};
+    use crate::tuple::Ysc2TupleHash;
+    use crate::variant::Ysc2_512;
+    use crate::{Ysc2_512TupleHash, Ysc2_512TupleHashXof};
+    use digest::consts::U32;
+
+    let custom = b"My Tuple App";
+    let items: [&[u8]; 3] = [b"alpha", b"", b"gamma"];
+    let expected = |items: &[&[u8]], length_bits: u64, out: &mut [u8]| {
+        let mut hasher = Ysc2CustomHasher::<Ysc2_512>::new(b"TupleHash", custom);
+        for item in items {
+            hasher.update(&encode_string(item));
+        }
+        hasher.update(&right_encode(length_bits));
+        hasher.finalize_xof().read(out);
+    };
+    let tuple_hash = |items: &[&[u8]]| {
+        let mut hash = Ysc2_512TupleHash::new(custom);
+        for item in items {
+            hash.update_item(item);
+        }
+        hash.finalize()
+    };
+
+    let digest = tuple_hash(&items);
+    let mut reference = [0u8; 64];
+    expected(&items, 512, &mut reference);
+    assert_eq!(digest[..], reference);
+
+    // 항목 경계가 바뀌거나 빈 항목이 더해지면 해시가 달라집니다.
+    assert_ne!(tuple_hash(&[b"ab", b"c"]), tuple_hash(&[b"a", b"bc"]));
+    assert_ne!(tuple_hash(&[b"abc"]), tuple_hash(&[b"abc", b""]));
+    assert_ne!(tuple_hash(&[]), tuple_hash(&[b""]));
+
+    let mut short = Ysc2TupleHash::<Ysc2_512, U32>::new(custom);
+    for item in items {
+        short.update_item(item);
+    }
+    let short = short.finalize();
+    expected(&items, 256, &mut reference[..32]);
+    assert_eq!(short[..], reference[..32]);
+    assert_ne!(short[..], digest[..32]);
+
+    let mut xof = Ysc2_512TupleHashXof::new(custom);
+    for item in items {
+        xof.update_item(item);
+    }
+    let mut stream = [0u8; 100];
+    xof.finalize_xof().read(&mut stream);
+    let mut reference = [0u8; 100];
+    expected(&items, 0, &mut reference);
+    assert_eq!(stream, reference);
+
+    let hex: String = digest[..16].iter().map(|b| format!("{b:02x}")).collect();
+    assert_eq!(hex, "c15a9a472a92e70352f1813ca426b095");
+}
+
+#[cfg(feature = "ysc2x")]
+#[test]
+fn ysc2_parallel_hash_matches_construction() {
+    use crate::custom::{left_encode, right_encode, Ysc2CustomHasher};
+    use crate::parallel::Ysc2ParallelHash;
+    use crate::variant::Ysc2_512;
+    use crate::{Ysc2_512ParallelHash, Ysc2_512ParallelHashXof};
+    use digest::{consts::U32, FixedOutput};
+
+    let custom = b"My Parallel App";
+    let message: Vec<u8> = (0..1000u32).map(|i| (i * 7 + 3) as u8).collect();
+    let expected = |block_size: usize, length_bits: u64, out: &mut [u8]| {
+        let mut hasher = Ysc2CustomHasher::<Ysc2_512>::new(b"ParallelHash", custom);
+        hasher.update(&left_encode(block_size as u64));
+        let mut blocks = 0u64;
+        for block in message.chunks(block_size) {
+            let mut chaining_value = [0u8; 64];
+            let mut block_hasher = Ysc2_512Hasher::default();
+            block_hasher.update(block);
+            block_hasher.finalize_xof().read(&mut chaining_value);
+            hasher.update(&chaining_value);
+            blocks += 1;
+        }
+        hasher.update(&right_encode(blocks));
+        hasher.update(&right_encode(length_bits));
+        hasher.finalize_xof().read(out);
+    };
+
+    let mut reference = [0u8; 64];
+    let mut digests = Vec::new();
+    for block_size in [1, 64, 100, 1000, 4096] {
+        // update 호출의 분할 방식과 무관해야 합니다.
+        let mut whole = Ysc2_512ParallelHash::new(block_size, custom);
+        whole.update(&message);
+        let digest = whole.finalize_fixed();
+        for step in [1, 63, 65, 333] {
+            let mut split = Ysc2_512ParallelHash::new(block_size, custom);
+            for piece in message.chunks(step) {
+                split.update(piece);
+            }
+            assert_eq!(split.finalize_fixed(), digest, "block size {block_size}, step {step}");
+        }
+        expected(block_size, 512, &mut reference);
+        assert_eq!(digest[..], reference, "block size {block_size}");
+        digests.push(digest);
+    }
+    // 블록 크기가 계산에 묶입니다 (1000과 4096은 블록 수가 같아도 다릅니다).
+    for (i, a) in digests.iter().enumerate() {
+        for b in &digests[i + 1..] {
+            assert_ne!(a, b);
+        }
+    }
+
+    let mut short = Ysc2ParallelHash::<Ysc2_512, U32>::new(100, custom);
+    short.update(&message);
+    let short = short.finalize_fixed();
+    expected(100, 256, &mut reference[..32]);
+    assert_eq!(short[..], reference[..32]);
+
+    let mut xof = Ysc2_512ParallelHashXof::new(100, custom);
+    xof.update(&message);
+    let mut stream = [0u8; 100];
+    xof.finalize_xof().read(&mut stream);
+    let mut reference = [0u8; 100];
+    expected(100, 0, &mut reference);
+    assert_eq!(stream, reference);
+
+    let hex: String = digests[2][..16].iter().map(|b| format!("{b:02x}")).collect();
+    assert_eq!(hex, "1d1a3743384f43c2f00969af166d8831");
+}
+
+#[cfg(feature = "ysc2x")]
+#[test]
+#[should_panic(expected = "block size must be non-zero")]
+fn ysc2_parallel_hash_rejects_empty_blocks() {
+    let _ = crate::Ysc2_512ParallelHash::new(0, b"");
+}
+
+#[cfg(feature = "ysc2x")]
+#[test]
+fn ysc2_tree_hash_matches_construction() {
+    use crate::custom::right_encode;
+    use crate::sponge::{Hasher, Ysc2xCore};
+    use crate::tree::{CHUNK_BYTES, LEAF_DOMAIN, ROOT_DOMAIN};
+    use crate::variant::Ysc2_512;
+    use crate::Ysc2_512TreeHasher;
+
+    let expected = |message: &[u8]| {
+        let mut root = Hasher::from_core(Ysc2xCore::<Ysc2_512>::with_capacity_domain(ROOT_DOMAIN));
+        for chunk in message.chunks(CHUNK_BYTES) {
+            let mut leaf = Hasher::from_core(Ysc2xCore::<Ysc2_512>::with_capacity_domain(LEAF_DOMAIN));
+            leaf.update(chunk);
+            let mut chaining_value = [0u8; 64];
+            leaf.finalize_xof().read(&mut chaining_value);
+            root.update(&chaining_value);
+        }
+        root.update(&right_encode(message.len().div_ceil(CHUNK_BYTES) as u64));
+        let mut out = [0u8; 100];
+        root.finalize_xof().read(&mut out);
+        out
+    };
+    let tree_hash = |hasher: Ysc2_512TreeHasher| {
+        let mut out = [0u8; 100];
+        hasher.finalize_xof().read(&mut out);
+        out
+    };
+
+    let message: Vec<u8> = (0..9 * CHUNK_BYTES + 123).map(|i| (i * 7 + 3) as u8).collect();
+    for len in [0, 1, CHUNK_BYTES - 1, CHUNK_BYTES, CHUNK_BYTES + 1, 4 * CHUNK_BYTES, 5 * CHUNK_BYTES + 64, message.len()] {
+        let message = &message[..len];
+        let reference = expected(message);
+        // 스레드 수나 update 분할 방식과 무관해야 합니다.
+        for threads in [1, 2, 3, 8] {
+            let mut whole = Ysc2_512TreeHasher::with_threads(threads);
+            whole.update(message);
+            assert_eq!(tree_hash(whole), reference, "length {len}, {threads} threads");
+        }
+        for step in [1000, 3 * CHUNK_BYTES + 1] {
+            let mut split = Ysc2_512TreeHasher::with_threads(4);
+            for piece in message.chunks(step) {
+                split.update(piece);
+            }
+            assert_eq!(tree_hash(split), reference, "length {len}, step {step}");
+        }
+    }
+
+    // 트리 해시는 일반 해시와 구분됩니다.
+    let mut plain = Ysc2_512Hasher::default();
+    plain.update(&message[..100]);
+    let mut plain_out = [0u8; 100];
+    plain.finalize_xof().read(&mut plain_out);
+    assert_ne!(plain_out, expected(&message[..100]));
+
+    let hex = |out: [u8; 100]| out[..16].iter().map(|b| format!("{b:02x}")).collect::<String>();
+    assert_eq!(hex(expected(b"")), "814102010010c0000000000000000000");
+    assert_eq!(hex(expected(&message)), "1edc7f2c2afead63efed856720c32478");
+}
+
+#[cfg(feature = "ysc2x")]
+#[test]
+fn ysc2_tree_hash_reader_and_file() {
+    use crate::tree::CHUNK_BYTES;
+    use crate::Ysc2_1024TreeHasher;
+
+    let message: Vec<u8> = (0..40 * CHUNK_BYTES + 5).map(|i| (i * 13 + 1) as u8).collect();
+    let digest = |hasher: Ysc2_1024TreeHasher| {
+        let mut out = [0u8; 64];
+        hasher.finalize_xof().read(&mut out);
+        out
+    };
+    let mut direct = Ysc2_1024TreeHasher::with_threads(2);
+    direct.update(&message);
+    let expected = digest(direct);
+
+    // 짧게 끊어 읽는 리더도 같은 결과를 냅니다.
+    let mut hasher = Ysc2_1024TreeHasher::with_threads(2);
+    let reader = std::io::Read::chain(&message[..12345], &message[12345..]);
+    assert_eq!(hasher.update_reader(reader).unwrap(), message.len() as u64);
+    assert_eq!(digest(hasher), expected);
+
+    let path = std::env::temp_dir().join(format!("ysc2-tree-hash-{}.bin", std::process::id()));
+    std::fs::write(&path, &message).unwrap();
+    let mut hasher = Ysc2_1024TreeHasher::with_threads(3);
+    let read = hasher.update_file(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(read.unwrap(), message.len() as u64);
+    assert_eq!(digest(hasher), expected);
+
+    assert!(Ysc2_1024TreeHasher::new().update_file(std::env::temp_dir().join("ysc2-no-such-file")).is_err());
+}
+
 #[cfg(feature = "ysc2x")]
 #[test]
 fn ysc2_1024_mac_verification() {
@@ -187,10 +1624,303 @@ fn ysc2_1024_aead_authentication_failure() {
     assert_eq!(err, aead::Error, "Error should be authentication error");
         
     // --- Case 3: Invalid tag ---
-    let mut invalid_tag_bytes = tag.clone();
-    invalid_tag_bytes[0] ^= 0xFF; // Flip the first byte of the tag
-    let invalid_tag = invalid_tag_bytes.into();
+    let mut invalid_tag = tag;
+    invalid_tag[0] ^= 0xFF; // Flip the first byte of the tag
     let err = cipher.decrypt_in_place_detached(&nonce, ASSOCIATED_DATA, &mut buffer, &invalid_tag)
         .expect_err("Decryption should fail for invalid tag");
     assert_eq!(err, aead::Error, "Error should be authentication error");
-}
\ No newline at end of file
+}
+
+#[cfg(feature = "ysc2x")]
+#[test]
+fn ysc2_aead_variable_length_key_and_nonce() {
+    let key = [0x1Fu8; 32];
+    let cipher = Ysc2_512Aead::new_variable(&key).expect("32-byte keys should be accepted");
+    let nonce = [0x2Eu8; 12];
+
+    let mut buffer = PLAINTEXT.to_vec();
+    let tag = cipher.encrypt_in_place_detached_with_nonce(&nonce, ASSOCIATED_DATA, &mut buffer)
+        .expect("AEAD encryption failed");
+
+    // 길이가 다른 Nonce는 인증에 실패해야 합니다.
+    let mut tampered = buffer.clone();
+    let err = cipher.decrypt_in_place_detached_with_nonce(&[0x2E; 13], ASSOCIATED_DATA, &mut tampered, &tag)
+        .expect_err("Decryption should fail for a different nonce length");
+    assert_eq!(err, aead::Error, "Error should be authentication error");
+
+    cipher.decrypt_in_place_detached_with_nonce(&nonce, ASSOCIATED_DATA, &mut buffer, &tag)
+        .expect("AEAD decryption should succeed with correct tag");
+    assert_eq!(buffer, PLAINTEXT, "AEAD decrypted text should match original");
+
+    // 가변 길이 Nonce 경로는 고정 길이 Nonce에서도 트레잇 메서드와 분리됩니다.
+    let fixed = Ysc2_512Aead::new(&[0x1F; 64].into());
+    let mut a = PLAINTEXT.to_vec();
+    let mut b = PLAINTEXT.to_vec();
+    let tag_a = fixed.encrypt_in_place_detached(&[0x2E; 64].into(), ASSOCIATED_DATA, &mut a).unwrap();
+    let tag_b = fixed.encrypt_in_place_detached_with_nonce(&[0x2E; 64], ASSOCIATED_DATA, &mut b).unwrap();
+    assert_ne!(tag_a, tag_b, "The variable-length nonce path should be separate from the trait method");
+
+    let mut c = PLAINTEXT.to_vec();
+    let variable = Ysc2_512Aead::new_variable(&[0x1F; 64]).unwrap();
+    let tag_c = variable.encrypt_in_place_detached(&[0x2E; 64].into(), ASSOCIATED_DATA, &mut c).unwrap();
+    assert_ne!(tag_c, tag_b, "The variable-length key path should be separate from the fixed-size one");
+
+    assert!(Ysc2_512Aead::new_variable(&[0u8; 15]).is_err(), "Keys under 16 bytes should be rejected");
+}
+
+#[cfg(feature = "ysc2x")]
+#[test]
+fn ysc2_aead_variable_nonces_do_not_collide() {
+    let cipher = Ysc2_512Aead::new_variable(&[0x3Cu8; 32]).unwrap();
+    let tag = |nonce: &[u8]| {
+        let mut buffer = PLAINTEXT.to_vec();
+        let tag = cipher.encrypt_in_place_detached_with_nonce(nonce, ASSOCIATED_DATA, &mut buffer).unwrap();
+        (buffer, tag)
+    };
+
+    // 빈 Nonce와 0 바이트 64개
+    assert_ne!(tag(&[]), tag(&[0u8; 64]), "An empty nonce should not collide with a zero block");
+    // 128바이트 A || B 와 64바이트 A ^ B
+    let a: [u8; 64] = core::array::from_fn(|i| i as u8);
+    let b: [u8; 64] = core::array::from_fn(|i| 0xF0 ^ i as u8);
+    let concatenated: Vec<u8> = a.iter().chain(&b).copied().collect();
+    let xored: Vec<u8> = a.iter().zip(&b).map(|(x, y)| x ^ y).collect();
+    assert_ne!(tag(&concatenated), tag(&xored), "A || B should not collide with A ^ B");
+    // 끝에 0을 붙인 Nonce
+    assert_ne!(tag(&[7u8; 12]), tag(&[7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 0]), "Zero-extended nonces should not collide");
+}
+
+#[cfg(feature = "ysc2x")]
+#[test]
+fn xysc2_aead_roundtrip_and_nonce_binding() {
+    use crate::variant::Ysc2_1024;
+    use crate::xysc2::derive_subkey;
+    use crate::XYsc2_1024Aead;
+
+    let mut rng = ChaCha8Rng::from_seed([14; 32]);
+    let key = XYsc2_1024Aead::generate_key(&mut rng);
+    let cipher = XYsc2_1024Aead::new(&key);
+    let nonce: [u8; 128] = core::array::from_fn(|i| i as u8);
+
+    let mut buffer = PLAINTEXT.to_vec();
+    let tag = cipher.encrypt_in_place_detached(&nonce.into(), ASSOCIATED_DATA, &mut buffer)
+        .expect("AEAD encryption failed");
+
+    // 하위 키를 쓰는 기본 AEAD와 같은 결과여야 합니다.
+    let subkey = derive_subkey::<Ysc2_1024>(&key, nonce[..64].try_into().unwrap());
+    let mut expected = PLAINTEXT.to_vec();
+    let expected_tag = Ysc2_1024Aead::new(&subkey)
+        .encrypt_in_place_detached(nonce[64..].into(), ASSOCIATED_DATA, &mut expected)
+        .expect("AEAD encryption failed");
+    assert_eq!((&buffer, tag), (&expected, expected_tag));
+
+    let mut other_nonce = nonce;
+    other_nonce[0] ^= 1;
+    let mut tampered = buffer.clone();
+    let err = cipher.decrypt_in_place_detached(&other_nonce.into(), ASSOCIATED_DATA, &mut tampered, &tag)
+        .expect_err("Decryption should fail under a different nonce prefix");
+    assert_eq!(err, aead::Error, "Error should be authentication error");
+
+    cipher.decrypt_in_place_detached(&nonce.into(), ASSOCIATED_DATA, &mut buffer, &tag)
+        .expect("AEAD decryption should succeed with correct tag");
+    assert_eq!(buffer, PLAINTEXT, "AEAD decrypted text should match original");
+}
+
+//======================================================================
+// Zeroization
+//======================================================================
+
+#[test]
+fn ysc2_secret_state_is_wiped_on_drop() {
+    use crate::stream::{Ysc2LegacyStreamCore, Ysc2SpongeStreamCore};
+    use crate::variant::{Ysc2_1024, Ysc2_512};
+    use crate::xysc2::XYsc2StreamCore;
+    use crate::Ysc2_512BlockCipher;
+    use zeroize::ZeroizeOnDrop;
+
+    let mut core = Ysc2SpongeStreamCore::<Ysc2_512>::new(&[0x5Au8; 64].into(), &[0xA5u8; 64].into());
+    cipher::StreamCipherCore::apply_keystream_blocks(&mut core, &mut [[0u8; 128].into(); 5]);
+    assert!(core.state.iter().any(|&w| w != 0));
+    assert!(wiped_on_drop(core), "Stream core state should be wiped");
+    assert!(wiped_on_drop(Ysc2LegacyStreamCore::<Ysc2_1024>::new(&[0x5Au8; 128].into(), &[0xA5u8; 64].into())));
+    assert!(wiped_on_drop(XYsc2StreamCore::<Ysc2_512>::new(&[0x5Au8; 64].into(), &[0xA5u8; 128].into())));
+    assert!(wiped_on_drop(<Ysc2_512BlockCipher as cipher::KeyInit>::new(&[0x5Au8; 64].into())));
+
+    // 래퍼 타입은 버퍼를 지우고, 코어가 스스로 지워집니다.
+    fn assert_zeroize_on_drop<T: ZeroizeOnDrop>() {}
+    assert_zeroize_on_drop::<Ysc2_512SpongeStreamCipher>();
+    assert_zeroize_on_drop::<Ysc2_1024SpongeStreamCipher>();
+    assert_zeroize_on_drop::<crate::XYsc2_512StreamCipher>();
+}
+
+#[cfg(feature = "ysc2x")]
+#[test]
+fn ysc2x_secret_state_is_wiped_on_drop() {
+    use crate::sponge::{Reader, Ysc2xCore};
+    use crate::variant::Ysc2_512;
+    use crate::XYsc2_512Aead;
+    use digest::core_api::{ExtendableOutputCore, XofReaderCore};
+
+    assert!(wiped_on_drop(Ysc2_512Aead::new(&[0x5Au8; 64].into())), "AEAD key state should be wiped");
+    assert!(wiped_on_drop(Ysc2_512Aead::new_variable(&[0x5Au8; 32]).unwrap()));
+    assert!(wiped_on_drop(XYsc2_512Aead::new(&[0x5Au8; 64].into())), "XYsc2 AEAD key should be wiped");
+
+    let mut core = <Ysc2xCore<Ysc2_512> as KeyInit>::new(&[0x5Au8; 64].into());
+    let mut reader: Reader<Ysc2_512> = core.finalize_xof_core(&mut Default::default());
+    reader.read_block();
+    assert!(wiped_on_drop(core), "Keyed sponge state should be wiped");
+    assert!(wiped_on_drop(reader), "Sponge reader state should be wiped");
+}
+
+//======================================================================
+// Cascade (YSC2 + AuxCrypt)
+//======================================================================
+
+#[cfg(feature = "auxcrypt")]
+#[test]
+fn cascade_stream_xors_both_keystreams() {
+    use crate::cascade::derive_subkeys;
+    use crate::variant::Ysc2_512;
+    use crate::Cascade512StreamCipher;
+    use auxcrypt::stream::AuxCryptStream;
+    use auxcrypt::variant::AuxCrypt512V3;
+
+    let master = [0x16u8; 64].into();
+    let nonce = [0x17u8; 64].into();
+    let (ysc2_key, auxcrypt_key) = derive_subkeys::<Ysc2_512, AuxCrypt512V3>(&master);
+    assert_ne!(ysc2_key, master);
+    assert_ne!(ysc2_key[..], auxcrypt_key[..], "The subkeys should be independent");
+
+    let mut keystream = [0u8; 600];
+    Cascade512StreamCipher::new(&master, &nonce).apply_keystream(&mut keystream);
+    let mut expected = [0u8; 600];
+    Ysc2_512SpongeStreamCipher::new(&ysc2_key, &nonce).apply_keystream(&mut expected);
+    AuxCryptStream::<AuxCrypt512V3>::new(&auxcrypt_key, &nonce).apply_keystream(&mut expected);
+    assert_eq!(keystream, expected, "The cascade keystream should be the XOR of both keystreams");
+
+    let mut tail = [0u8; 300];
+    let mut cipher = Cascade512StreamCipher::new(&master, &nonce);
+    cipher.seek(300u64);
+    cipher.apply_keystream(&mut tail);
+    assert_eq!(tail[..], keystream[300..], "Seeking should move both keystreams");
+    assert_eq!(cipher.current_pos::<u64>(), 600);
+}
+
+#[cfg(all(feature = "auxcrypt", feature = "ysc2x"))]
+#[test]
+fn cascade_aead_roundtrip_and_layers() {
+    use crate::cascade::derive_subkeys;
+    use crate::variant::Ysc2_1024;
+    use crate::Cascade1024Aead;
+    use auxcrypt::variant::AuxCrypt1024V3;
+    use auxcrypt::stream::AuxCryptStream;
+
+    let master = [0x18u8; 128].into();
+    let nonce = [0x19u8; 64].into();
+    let cipher = Cascade1024Aead::new(&master);
+
+    let mut buffer = PLAINTEXT.to_vec();
+    let tag = cipher.encrypt_in_place_detached(&nonce, ASSOCIATED_DATA, &mut buffer)
+        .expect("AEAD encryption failed");
+
+    // AuxCrypt 스트림으로 먼저 암호화한 뒤 YSC2 AEAD로 봉인한 결과와 같아야 합니다.
+    let (ysc2_key, auxcrypt_key) = derive_subkeys::<Ysc2_1024, AuxCrypt1024V3>(&master);
+    let mut expected = PLAINTEXT.to_vec();
+    AuxCryptStream::<AuxCrypt1024V3>::new(&auxcrypt_key, &nonce).apply_keystream(&mut expected);
+    let expected_tag = Ysc2_1024Aead::new(&ysc2_key)
+        .encrypt_in_place_detached(&nonce, ASSOCIATED_DATA, &mut expected)
+        .expect("AEAD encryption failed");
+    assert_eq!((&buffer, tag), (&expected, expected_tag));
+
+    let mut tampered = buffer.clone();
+    tampered[3] ^= 0x10;
+    let err = cipher.decrypt_in_place_detached(&nonce, ASSOCIATED_DATA, &mut tampered, &tag)
+        .expect_err("Decryption should fail for tampered ciphertext");
+    assert_eq!(err, aead::Error, "Error should be authentication error");
+
+    cipher.decrypt_in_place_detached(&nonce, ASSOCIATED_DATA, &mut buffer, &tag)
+        .expect("AEAD decryption should succeed with correct tag");
+    assert_eq!(buffer, PLAINTEXT, "AEAD decrypted text should match original");
+}
+
+#[cfg(feature = "auxcrypt")]
+#[test]
+fn dual_permutation_hasher_matches_construction() {
+    use crate::variant::Ysc2_512;
+    use crate::{DualPermutation512Hasher, Ysc2State};
+    use auxcrypt::variant::AuxCrypt512V3;
+    use auxcrypt::AuxCryptState;
+
+    // 모듈 문서의 구성을 공개 순열 API로 직접 따라갑니다.
+    let message = [0x3Cu8; 100];
+    let mut padded = message.to_vec();
+    padded.push(0x80);
+    padded.resize(128, 0);
+    let mut capacity = [0u8; 64];
+    capacity[..14].copy_from_slice(b"YSC2-DUAL-HASH");
+
+    let mut ysc2 = Ysc2State::new();
+    let mut aux = AuxCryptState::new();
+    capacity[14] = 0x01;
+    ysc2.xor_in(64, &capacity);
+    capacity[14] = 0x02;
+    aux.xor_in(64, &capacity);
+    for block in padded.chunks(64) {
+        ysc2.xor_in(0, block);
+        ysc2.permute::<Ysc2_512>();
+        aux.xor_in(0, block);
+        aux.permute::<AuxCrypt512V3>();
+    }
+    let mut expected = [0u8; 192];
+    for block in expected.chunks_mut(64) {
+        ysc2.permute::<Ysc2_512>();
+        aux.permute::<AuxCrypt512V3>();
+        let (mut y, mut a) = ([0u8; 64], [0u8; 64]);
+        ysc2.extract(0, &mut y);
+        aux.extract(0, &mut a);
+        for (out, (y, a)) in block.iter_mut().zip(y.iter().zip(a.iter())) {
+            *out = y ^ a;
+        }
+    }
+
+    let mut hasher = DualPermutation512Hasher::default();
+    hasher.update(&message[..33]);
+    hasher.update(&message[33..]);
+    let mut output = [0u8; 192];
+    hasher.finalize_xof().read(&mut output);
+    assert_eq!(output, expected);
+}
+
+#[cfg(feature = "auxcrypt")]
+#[test]
+fn dual_permutation_test_vectors() {
+    use crate::dual::dual_kdf;
+    use crate::variant::Ysc2_512;
+    use crate::{DualPermutation1024Hasher, DualPermutation512Hasher};
+    use auxcrypt::variant::AuxCrypt512V3;
+
+    let hex = |bytes: &[u8]| bytes.iter().map(|b| format!("{b:02x}")).collect::<String>();
+    let mut out = [0u8; 32];
+
+    let mut hasher = DualPermutation512Hasher::default();
+    hasher.update(b"abc");
+    hasher.finalize_xof().read(&mut out);
+    assert_eq!(hex(&out), "b3ce4f42eaa6423d22ed7436b618660a6d539723d3ca474c617c951f3f03601f");
+
+    let mut hasher = DualPermutation1024Hasher::default();
+    hasher.update(b"abc");
+    hasher.finalize_xof().read(&mut out);
+    assert_eq!(hex(&out), "56f5e59965825dc449f2cf2cda668d19a4e1f96bb3103ef53362d188d87504f5");
+
+    let mut okm = [0u8; 48];
+    dual_kdf::<Ysc2_512, AuxCrypt512V3>(b"input keying material", b"salt", b"context", &mut okm);
+    assert_eq!(
+        hex(&okm),
+        "0d544f856c43ba3d63598774239b2532981bbf4a3077c7a445293dc375d992effae495c522f3ddc0611783156619bb2d"
+    );
+
+    // 필드 경계가 길이 접두어로 구분되어야 합니다.
+    let mut shifted = [0u8; 48];
+    dual_kdf::<Ysc2_512, AuxCrypt512V3>(b"input keying material", b"sal", b"tcontext", &mut shifted);
+    assert_ne!(okm, shifted);
+}
@@ -0,0 +1,219 @@
+//======================================================================
+// src/tree.rs
+// 큰 입력을 청크 단위로 여러 스레드와 SIMD 레인에서 해시하는 트리 해시 모드
+//======================================================================
+
+//! Tree hashing of large inputs, in the style of KangarooTwelve.
+//!
+//! The input `X` is split into chunks `X0, ..., X(n-1)` of [`CHUNK_BYTES`]
+//! bytes (the last may be shorter; an empty input has no chunks). Each chunk
+//! is a leaf, hashed on its own to a 64-byte chaining value, and a final node
+//! combines the chaining values:
+//!
+//! ```text
+//! Zi            = Leaf(Xi)[..64]
+//! TreeHash(X)   = Root(Z0 || ... || Z(n-1) || right_encode(n))
+//! ```
+//!
+//! `Leaf` and `Root` are the YSC2-X sponge started with [`LEAF_DOMAIN`] and
+//! [`ROOT_DOMAIN`] in its capacity, so neither collides with the plain
+//! [`Hasher`] or with each other. The output is an XOF like the plain hasher.
+//!
+//! Only the input defines the output: full chunks are spread over
+//! `std::thread::scope` workers and hashed four at a time in SIMD lanes, but
+//! the thread count, the active backend and the way the input is split across
+//! `update` calls never change the result.
+
+use crate::backends::{Backend, LANES};
+use crate::consts::RATE_BYTES;
+use crate::custom::right_encode;
+use crate::sponge::{Hasher, Reader, Ysc2xCore};
+use crate::variant::Ysc2Variant;
+use digest::{core_api::XofReaderCoreWrapper, ExtendableOutput, HashMarker, Update, XofReader};
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+/// Length of a leaf chunk in bytes.
+pub const CHUNK_BYTES: usize = 8192;
+
+/// Domain separator placed in the capacity of leaf nodes.
+pub const LEAF_DOMAIN: &str = "YSC2-X-TREE-LEAF";
+
+/// Domain separator placed in the capacity of the final node.
+pub const ROOT_DOMAIN: &str = "YSC2-X-TREE";
+
+/// Length of a chunk chaining value in bytes.
+const CHAINING_VALUE_BYTES: usize = RATE_BYTES;
+
+/// 파일이나 리더에서 한 번에 읽어 들이는 양 (스레드당 청크 수).
+const READ_CHUNKS_PER_THREAD: usize = 16;
+
+type ChainingValue = [u8; CHAINING_VALUE_BYTES];
+
+/// 청크 하나(길이 무관)의 체이닝 값.
+fn chaining_value<V: Ysc2Variant>(chunk: &[u8]) -> ChainingValue {
+    let mut leaf = Hasher::from_core(Ysc2xCore::<V>::with_capacity_domain(LEAF_DOMAIN));
+    leaf.update(chunk);
+    let mut chaining_value = [0u8; CHAINING_VALUE_BYTES];
+    leaf.finalize_xof().read(&mut chaining_value);
+    chaining_value
+}
+
+/// 완전한 청크들의 체이닝 값을 순서대로 `out`에 추가합니다.
+/// `LANES`개씩 묶어 나란히 계산하고, 남는 청크는 하나씩 계산합니다.
+fn chaining_values<V: Ysc2Variant>(backend: Backend, chunks: &[u8], out: &mut Vec<ChainingValue>) {
+    let leaf = Ysc2xCore::<V>::with_capacity_domain(LEAF_DOMAIN);
+    let mut groups = chunks.chunks_exact(LANES * CHUNK_BYTES);
+    for group in &mut groups {
+        let inputs = core::array::from_fn(|lane| &group[lane * CHUNK_BYTES..(lane + 1) * CHUNK_BYTES]);
+        out.extend(leaf.finalize_x4(backend, inputs));
+    }
+    out.extend(groups.remainder().chunks(CHUNK_BYTES).map(chaining_value::<V>));
+}
+
+/// 완전한 청크들을 최대 `threads`개의 스레드에 연속 구간으로 나누어 해시합니다.
+/// 결과는 청크 순서대로 모으므로 스레드 수와 무관합니다.
+fn chaining_values_threaded<V: Ysc2Variant>(chunks: &[u8], threads: usize) -> Vec<ChainingValue> {
+    let backend = Backend::active();
+    let count = chunks.len() / CHUNK_BYTES;
+    let mut out = Vec::with_capacity(count);
+    // 각 스레드가 SIMD 레인을 채울 수 있도록 LANES 배수의 청크를 맡깁니다.
+    let per_thread = count.div_ceil(threads).next_multiple_of(LANES);
+    if per_thread >= count {
+        chaining_values::<V>(backend, chunks, &mut out);
+        return out;
+    }
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = chunks
+            .chunks(per_thread * CHUNK_BYTES)
+            .map(|part| {
+                scope.spawn(move || {
+                    let mut values = Vec::with_capacity(per_thread);
+                    chaining_values::<V>(backend, part, &mut values);
+                    values
+                })
+            })
+            .collect();
+        for worker in workers {
+            out.extend(worker.join().expect("tree hash worker panicked"));
+        }
+    });
+    out
+}
+
+/// Tree hasher for large inputs (see the [module docs](self)).
+#[derive(Clone)]
+pub struct Ysc2TreeHasher<V: Ysc2Variant> {
+    root: Hasher<V>,
+    partial: Vec<u8>,
+    chunks: u64,
+    threads: usize,
+}
+
+impl<V: Ysc2Variant> Ysc2TreeHasher<V> {
+    /// Creates a hasher that uses one thread per available CPU.
+    pub fn new() -> Self {
+        Self::with_threads(std::thread::available_parallelism().map_or(1, |threads| threads.get()))
+    }
+
+    /// Creates a hasher that uses up to `threads` threads per `update` call.
+    ///
+    /// The thread count only affects speed, never the output.
+    ///
+    /// # Panics
+    /// Panics if `threads` is zero.
+    pub fn with_threads(threads: usize) -> Self {
+        assert!(threads > 0, "tree hash needs at least one thread");
+        Self {
+            root: Hasher::from_core(Ysc2xCore::with_capacity_domain(ROOT_DOMAIN)),
+            partial: Vec::with_capacity(CHUNK_BYTES),
+            chunks: 0,
+            threads,
+        }
+    }
+
+    /// Hashes everything `reader` yields until end of file and returns the
+    /// number of bytes read.
+    ///
+    /// The input is read in batches large enough to keep every thread busy.
+    pub fn update_reader<R: io::Read>(&mut self, mut reader: R) -> io::Result<u64> {
+        let mut batch = vec![0u8; self.threads * READ_CHUNKS_PER_THREAD * CHUNK_BYTES];
+        let mut total = 0;
+        loop {
+            let mut filled = 0;
+            while filled < batch.len() {
+                match reader.read(&mut batch[filled..]) {
+                    Ok(0) => break,
+                    Ok(n) => filled += n,
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                    Err(e) => return Err(e),
+                }
+            }
+            self.update(&batch[..filled]);
+            total += filled as u64;
+            if filled < batch.len() {
+                return Ok(total);
+            }
+        }
+    }
+
+    /// Hashes the whole file at `path` and returns its length.
+    ///
+    /// Gives the same result as mapping the file and passing it to `update`
+    /// in one call, without the `unsafe` of memory mapping.
+    pub fn update_file<P: AsRef<Path>>(&mut self, path: P) -> io::Result<u64> {
+        self.update_reader(File::open(path)?)
+    }
+
+    fn absorb_chaining_values(&mut self, values: &[ChainingValue]) {
+        for value in values {
+            self.root.update(value);
+        }
+        self.chunks += values.len() as u64;
+    }
+}
+
+impl<V: Ysc2Variant> Default for Ysc2TreeHasher<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: Ysc2Variant> HashMarker for Ysc2TreeHasher<V> {}
+
+impl<V: Ysc2Variant> Update for Ysc2TreeHasher<V> {
+    fn update(&mut self, mut data: &[u8]) {
+        if !self.partial.is_empty() {
+            let take = data.len().min(CHUNK_BYTES - self.partial.len());
+            self.partial.extend_from_slice(&data[..take]);
+            data = &data[take..];
+            if self.partial.len() < CHUNK_BYTES {
+                return;
+            }
+            let value = chaining_value::<V>(&self.partial);
+            self.absorb_chaining_values(&[value]);
+            self.partial.clear();
+        }
+        // 완전한 청크는 바로 해시합니다 (마지막 청크인지와 무관하게 체이닝 값이 같습니다).
+        let full = data.len() - data.len() % CHUNK_BYTES;
+        if full > 0 {
+            let values = chaining_values_threaded::<V>(&data[..full], self.threads);
+            self.absorb_chaining_values(&values);
+        }
+        self.partial.extend_from_slice(&data[full..]);
+    }
+}
+
+impl<V: Ysc2Variant> ExtendableOutput for Ysc2TreeHasher<V> {
+    type Reader = XofReaderCoreWrapper<Reader<V>>;
+
+    fn finalize_xof(mut self) -> Self::Reader {
+        if !self.partial.is_empty() {
+            let value = chaining_value::<V>(&self.partial);
+            self.absorb_chaining_values(&[value]);
+        }
+        self.root.update(&right_encode(self.chunks));
+        self.root.finalize_xof()
+    }
+}
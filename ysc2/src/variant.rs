@@ -1,3 +1,18 @@
+use core::marker::PhantomData;
+
+/// Revision of the YSC2 permutation's round constants.
+///
+/// Every revision is a different permutation, so each variant pins one. The
+/// built-in variants keep [`Revision::V1`] so existing outputs stay reproducible.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Revision {
+    /// Iota constants `0, 1, 2, ...` XORed into word 0 ([`round_constant`](crate::consts::round_constant)).
+    V1,
+    /// LFSR-derived 64-bit constants XORed into all 16 words
+    /// ([`lfsr_round_constants`](crate::consts::lfsr_round_constants)).
+    V2,
+}
+
 // --- Security Parameter Abstraction ---
 /// YSC2 순열을 위한 핵심 트레잇입니다.
 /// 보안 수준별로 다른 파라미터(라운드 수, 키/Nonce 크기)를 정의합니다.
@@ -11,10 +26,27 @@ pub trait Ysc2Variant : Sized + Clone {
     
     const ROUNDS: usize;
 
+    /// Round-constant revision of the permutation.
+    const REVISION: Revision = Revision::V1;
+
     const KEYED_DOMAIN: &'static str;
 
     const AEAD_DOMAIN: &'static str;
 
+    /// Domain separator for the Even-Mansour block cipher key schedule.
+    ///
+    /// Defaults to [`KEYED_DOMAIN`](Self::KEYED_DOMAIN): the key schedule
+    /// places it at the end of the state next to a key index byte, a layout
+    /// the keyed sponge never produces.
+    const BLOCK_CIPHER_DOMAIN: &'static str = Self::KEYED_DOMAIN;
+
+    /// Domain separator for the stream cipher initialisation (at most 48 bytes).
+    ///
+    /// Defaults to [`KEYED_DOMAIN`](Self::KEYED_DOMAIN): the initialisation
+    /// places it in the capacity next to the parameter word, which already
+    /// keeps it apart from the keyed sponge.
+    const STREAM_DOMAIN: &'static str = Self::KEYED_DOMAIN;
+
     const AEAD_NONCE_DOMAIN: &'static str = "NONCE";
     const AEAD_AD_DOMAIN: &'static str = "AD";
     const AEAD_CT_DOMAIN: &'static str = "CT";
@@ -35,6 +67,8 @@ impl Ysc2Variant for Ysc2_512 {
 
     const KEYED_DOMAIN: &'static str = "YSC2-X-MAC-512";
     const AEAD_DOMAIN: &'static str = "YSC2-512-AEAD-V1";
+    const BLOCK_CIPHER_DOMAIN: &'static str = "YSC2-512-EM-V1";
+    const STREAM_DOMAIN: &'static str = "YSC2-512-STREAM-V2";
 }
 
 /// YSC2 variant with a 1024-bit key and 512-bit nonce.
@@ -50,4 +84,88 @@ impl Ysc2Variant for Ysc2_1024 {
     const NONCE_SIZE: usize = 64;
     const KEYED_DOMAIN: &'static str = "YSC2-X-MAC-1024";
     const AEAD_DOMAIN: &'static str = "YSC2-1024-AEAD-V1";
-}
\ No newline at end of file
+    const BLOCK_CIPHER_DOMAIN: &'static str = "YSC2-1024-EM-V1";
+    const STREAM_DOMAIN: &'static str = "YSC2-1024-STREAM-V2";
+}
+
+/// [`Ysc2_512`] with `R` rounds instead of 12.
+///
+/// Fewer rounds lower the FHE depth and allow cryptanalysis of reduced-round
+/// versions; `Ysc2_512R<12>` computes exactly the same outputs as [`Ysc2_512`].
+#[derive(Clone)]
+pub struct Ysc2_512R<const R: usize>;
+impl<const R: usize> Ysc2Variant for Ysc2_512R<R> {
+    type KeySize = cipher::consts::U64;
+    type NonceSize = cipher::consts::U64;
+    const ROUNDS: usize = {
+        assert!(R > 0, "a YSC2 variant needs at least one round");
+        R
+    };
+
+    const KEY_SIZE: usize = 64;
+
+    const NONCE_SIZE: usize = 64;
+
+    const KEYED_DOMAIN: &'static str = Ysc2_512::KEYED_DOMAIN;
+    const AEAD_DOMAIN: &'static str = Ysc2_512::AEAD_DOMAIN;
+    const BLOCK_CIPHER_DOMAIN: &'static str = Ysc2_512::BLOCK_CIPHER_DOMAIN;
+    const STREAM_DOMAIN: &'static str = Ysc2_512::STREAM_DOMAIN;
+}
+
+/// [`Ysc2_1024`] with `R` rounds instead of 12.
+///
+/// `Ysc2_1024R<12>` computes exactly the same outputs as [`Ysc2_1024`].
+#[derive(Clone)]
+pub struct Ysc2_1024R<const R: usize>;
+impl<const R: usize> Ysc2Variant for Ysc2_1024R<R> {
+    type KeySize = cipher::consts::U128;
+    type NonceSize = cipher::consts::U64;
+    const ROUNDS: usize = {
+        assert!(R > 0, "a YSC2 variant needs at least one round");
+        R
+    };
+
+    const KEY_SIZE: usize = 128;
+
+    const NONCE_SIZE: usize = 64;
+    const KEYED_DOMAIN: &'static str = Ysc2_1024::KEYED_DOMAIN;
+    const AEAD_DOMAIN: &'static str = Ysc2_1024::AEAD_DOMAIN;
+    const BLOCK_CIPHER_DOMAIN: &'static str = Ysc2_1024::BLOCK_CIPHER_DOMAIN;
+    const STREAM_DOMAIN: &'static str = Ysc2_1024::STREAM_DOMAIN;
+}
+
+/// Variant `V` with the [`Revision::V2`] (LFSR) round constants.
+///
+/// Key and nonce sizes, round count and domain separators are those of `V`.
+pub struct Rev2<V: Ysc2Variant>(PhantomData<V>);
+
+impl<V: Ysc2Variant> Clone for Rev2<V> {
+    fn clone(&self) -> Self {
+        Rev2(PhantomData)
+    }
+}
+
+impl<V: Ysc2Variant> Ysc2Variant for Rev2<V> {
+    type KeySize = V::KeySize;
+    type NonceSize = V::NonceSize;
+    const ROUNDS: usize = V::ROUNDS;
+    const REVISION: Revision = Revision::V2;
+
+    const KEY_SIZE: usize = V::KEY_SIZE;
+
+    const NONCE_SIZE: usize = V::NONCE_SIZE;
+
+    const KEYED_DOMAIN: &'static str = V::KEYED_DOMAIN;
+    const AEAD_DOMAIN: &'static str = V::AEAD_DOMAIN;
+    const BLOCK_CIPHER_DOMAIN: &'static str = V::BLOCK_CIPHER_DOMAIN;
+    const STREAM_DOMAIN: &'static str = V::STREAM_DOMAIN;
+    const AEAD_NONCE_DOMAIN: &'static str = V::AEAD_NONCE_DOMAIN;
+    const AEAD_AD_DOMAIN: &'static str = V::AEAD_AD_DOMAIN;
+    const AEAD_CT_DOMAIN: &'static str = V::AEAD_CT_DOMAIN;
+}
+
+/// [`Ysc2_512`] with LFSR round constants.
+pub type Ysc2_512V2 = Rev2<Ysc2_512>;
+
+/// [`Ysc2_1024`] with LFSR round constants.
+pub type Ysc2_1024V2 = Rev2<Ysc2_1024>;
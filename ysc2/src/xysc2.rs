@@ -0,0 +1,184 @@
+//======================================================================
+// src/xysc2.rs
+// 확장 Nonce 구성 (XYsc2)
+//
+// Nonce의 앞부분과 키로 메시지별 하위 키를 유도하고(HChaCha 방식),
+// 나머지 Nonce로 기존 스트림 암호 / AEAD를 그대로 실행합니다.
+//======================================================================
+
+use crate::consts::RATE_BYTES;
+use crate::stream::{init_state, squeeze_key, BlockPos, Ysc2SpongeStreamCore, MODE_SUBKEY};
+use crate::variant::Ysc2Variant;
+use cipher::{
+    consts::{U128, U64},
+    generic_array::GenericArray,
+    BlockSizeUser, Iv, IvSizeUser, Key, KeyIvInit, KeySizeUser, StreamCipherCore, StreamCipherCoreWrapper,
+    StreamCipherSeekCore,
+};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Length of the nonce prefix consumed by the subkey derivation.
+pub const XNONCE_PREFIX_BYTES: usize = RATE_BYTES;
+
+/// Size of an extended nonce: the 64-byte prefix plus the 64-byte inner nonce.
+pub type XNonceSize = U128;
+
+/// Derives the per-message subkey from `key` and the first
+/// [`XNONCE_PREFIX_BYTES`] of an extended nonce.
+///
+/// The key and the prefix are absorbed like the sponge stream
+/// initialisation, but with the subkey mode in the capacity (see
+/// [`stream_iv`](crate::stream::stream_iv)). The subkey is then squeezed from
+/// the rate only, so it does not reveal `key` directly; as for the stream
+/// (see [`Sponge`](crate::stream::Sponge)), the hidden capacity only protects
+/// each word pair on its own.
+pub fn derive_subkey<V: Ysc2Variant>(
+    key: &GenericArray<u8, V::KeySize>,
+    nonce_prefix: &[u8; XNONCE_PREFIX_BYTES],
+) -> GenericArray<u8, V::KeySize> {
+    let mut subkey = GenericArray::default();
+    squeeze_key::<V>(init_state::<V>(MODE_SUBKEY, key, nonce_prefix), &mut subkey);
+    subkey
+}
+
+/// 확장 Nonce를 (하위 키 유도용 앞부분, 내부 Nonce)로 나눕니다.
+fn split_nonce(nonce: &[u8]) -> (&[u8; XNONCE_PREFIX_BYTES], &GenericArray<u8, U64>) {
+    let (prefix, inner) = nonce.split_at(XNONCE_PREFIX_BYTES);
+    (prefix.try_into().unwrap(), GenericArray::from_slice(inner))
+}
+
+/// The XYsc2 stream cipher core: the sponge YSC2 stream keyed with the
+/// subkey of [`derive_subkey`] and the second half of the extended nonce.
+///
+/// With a 1024-bit nonce, nonces can be drawn at random for any practical
+/// number of messages under one long-lived key.
+#[derive(Zeroize, ZeroizeOnDrop)]
+pub struct XYsc2StreamCore<V: Ysc2Variant<NonceSize = U64>>(Ysc2SpongeStreamCore<V>);
+
+/// The XYsc2 stream cipher with 128-byte nonces.
+pub type XYsc2StreamCipher<V> = StreamCipherCoreWrapper<XYsc2StreamCore<V>>;
+
+impl<V: Ysc2Variant<NonceSize = U64>> KeySizeUser for XYsc2StreamCore<V> {
+    type KeySize = V::KeySize;
+}
+
+impl<V: Ysc2Variant<NonceSize = U64>> IvSizeUser for XYsc2StreamCore<V> {
+    type IvSize = XNonceSize;
+}
+
+impl<V: Ysc2Variant<NonceSize = U64>> BlockSizeUser for XYsc2StreamCore<V> {
+    type BlockSize = U128;
+}
+
+impl<V: Ysc2Variant<NonceSize = U64>> KeyIvInit for XYsc2StreamCore<V> {
+    fn new(key: &Key<Self>, iv: &Iv<Self>) -> Self {
+        let (prefix, nonce) = split_nonce(iv);
+        let mut subkey = derive_subkey::<V>(key, prefix);
+        let core = Ysc2SpongeStreamCore::<V>::new(&subkey, nonce);
+        subkey.as_mut_slice().zeroize();
+        Self(core)
+    }
+}
+
+impl<V: Ysc2Variant<NonceSize = U64>> StreamCipherCore for XYsc2StreamCore<V> {
+    fn remaining_blocks(&self) -> Option<usize> {
+        self.0.remaining_blocks()
+    }
+
+    fn process_with_backend(&mut self, f: impl cipher::StreamClosure<BlockSize = Self::BlockSize>) {
+        self.0.process_with_backend(f);
+    }
+}
+
+impl<V: Ysc2Variant<NonceSize = U64>> StreamCipherSeekCore for XYsc2StreamCore<V> {
+    type Counter = BlockPos;
+
+    fn get_block_pos(&self) -> Self::Counter {
+        self.0.get_block_pos()
+    }
+
+    fn set_block_pos(&mut self, pos: Self::Counter) {
+        self.0.set_block_pos(pos);
+    }
+}
+
+#[cfg(feature = "ysc2x")]
+pub use self::aead_impl::XYsc2Aead;
+
+#[cfg(feature = "ysc2x")]
+mod aead_impl {
+    use super::{derive_subkey, split_nonce, XNonceSize};
+    use crate::aead::Ysc2Aead;
+    use crate::variant::Ysc2Variant;
+    use aead::{consts::{U0, U16, U64}, AeadCore, AeadInPlace, Key, KeyInit, KeySizeUser, Nonce, Tag};
+    use zeroize::{Zeroize, ZeroizeOnDrop};
+
+    /// YSC2-X AEAD with 128-byte nonces.
+    ///
+    /// Each message runs [`Ysc2Aead`] under the subkey derived from the key and
+    /// the first half of the nonce, with the second half as its nonce.
+    #[derive(Clone)]
+    pub struct XYsc2Aead<V: Ysc2Variant<NonceSize = U64>> {
+        key: Key<Ysc2Aead<V>>,
+    }
+
+    impl<V: Ysc2Variant<NonceSize = U64>> XYsc2Aead<V> {
+        /// 메시지별 하위 키로 내부 AEAD를 만들고 나머지 Nonce와 함께 `f`를 실행합니다.
+        fn with_inner<T>(&self, nonce: &Nonce<Self>, f: impl FnOnce(&Ysc2Aead<V>, &Nonce<Ysc2Aead<V>>) -> T) -> T {
+            let (prefix, inner_nonce) = split_nonce(nonce);
+            let mut subkey = derive_subkey::<V>(&self.key, prefix);
+            let inner = Ysc2Aead::<V>::new(&subkey);
+            subkey.as_mut_slice().zeroize();
+            f(&inner, inner_nonce)
+        }
+    }
+
+    impl<V: Ysc2Variant<NonceSize = U64>> Drop for XYsc2Aead<V> {
+        fn drop(&mut self) {
+            self.key.as_mut_slice().zeroize();
+        }
+    }
+
+    impl<V: Ysc2Variant<NonceSize = U64>> ZeroizeOnDrop for XYsc2Aead<V> {}
+
+    impl<V: Ysc2Variant<NonceSize = U64>> KeySizeUser for XYsc2Aead<V> {
+        type KeySize = V::KeySize;
+    }
+
+    impl<V: Ysc2Variant<NonceSize = U64>> KeyInit for XYsc2Aead<V> {
+        fn new(key: &Key<Self>) -> Self {
+            Self { key: key.clone() }
+        }
+    }
+
+    impl<V: Ysc2Variant<NonceSize = U64>> AeadCore for XYsc2Aead<V> {
+        type NonceSize = XNonceSize;
+        type TagSize = U16;
+        type CiphertextOverhead = U0;
+    }
+
+    impl<V: Ysc2Variant<NonceSize = U64>> AeadInPlace for XYsc2Aead<V> {
+        fn encrypt_in_place_detached(
+            &self,
+            nonce: &Nonce<Self>,
+            associated_data: &[u8],
+            buffer: &mut [u8],
+        ) -> aead::Result<Tag<Self>> {
+            self.with_inner(nonce, |inner, inner_nonce| {
+                inner.encrypt_in_place_detached(inner_nonce, associated_data, buffer)
+            })
+        }
+
+        fn decrypt_in_place_detached(
+            &self,
+            nonce: &Nonce<Self>,
+            associated_data: &[u8],
+            buffer: &mut [u8],
+            tag: &Tag<Self>,
+        ) -> aead::Result<()> {
+            self.with_inner(nonce, |inner, inner_nonce| {
+                inner.decrypt_in_place_detached(inner_nonce, associated_data, buffer, tag)
+            })
+        }
+    }
+}